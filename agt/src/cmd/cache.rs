@@ -0,0 +1,107 @@
+use crate::{config, remote, ui};
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// List cached repository tarballs
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove cached tarballs
+    Clean {
+        /// Only remove tarballs of this [host/]owner or [host/]owner/repo
+        repo: Option<String>,
+    },
+}
+
+pub fn execute(action: CacheAction) -> Result<()> {
+    match action {
+        CacheAction::List { json } => list(json),
+        CacheAction::Clean { repo } => clean(repo.as_deref()),
+    }
+}
+
+fn list(json_output: bool) -> Result<()> {
+    let entries = remote::list_cache();
+
+    if json_output {
+        let output: Vec<serde_json::Value> = entries
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "host": e.host,
+                    "owner": e.owner,
+                    "repo": e.repo,
+                    "commit": e.commit,
+                    "size": e.size,
+                    "path": e.path.display().to_string(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    ui::section("Download Cache");
+    eprintln!("  {}", config::cache_dir().display());
+    eprintln!();
+
+    if entries.is_empty() {
+        eprintln!("  Cache is empty.");
+        eprintln!();
+        return Ok(());
+    }
+
+    let mut table = ui::table::new_table();
+    table.set_header(["Repository", "Commit", "Size"]);
+    for entry in &entries {
+        ui::table::add_row(&mut table, &[
+            &entry.label(),
+            remote::short_commit(&entry.commit),
+            &format_size(entry.size),
+        ]);
+    }
+    println!("{table}");
+
+    let total: u64 = entries.iter().map(|e| e.size).sum();
+    eprintln!();
+    eprintln!("  {} tarball(s), {} total", entries.len(), format_size(total));
+    eprintln!("  Use: agt cache clean [owner/repo]  to free space");
+    eprintln!();
+    Ok(())
+}
+
+fn clean(repo: Option<&str>) -> Result<()> {
+    let removed = remote::clean_cache(repo)?;
+
+    if removed.is_empty() {
+        ui::info("Nothing to clean");
+        return Ok(());
+    }
+
+    let freed: u64 = removed.iter().map(|e| e.size).sum();
+    ui::success(&format!(
+        "Removed {} cached tarball(s), freed {}",
+        removed.len(),
+        format_size(freed)
+    ));
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
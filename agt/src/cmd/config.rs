@@ -0,0 +1,152 @@
+use crate::{config, ui};
+use anyhow::{bail, Result};
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the effective value of a setting (e.g. llm.model)
+    Get {
+        /// Dotted key
+        key: String,
+    },
+    /// Write a setting to the user config file
+    Set {
+        /// Dotted key (agent, source, llm.cli, llm.model, llm.sandbox, paths.*, verify.*)
+        key: String,
+        /// Value; TOML syntax (true, 3, ["a"]) or a plain string
+        value: String,
+        /// Write to the project's .agents/agt.toml instead
+        #[arg(long, conflicts_with = "system")]
+        project: bool,
+        /// Write to the system config file instead
+        #[arg(long)]
+        system: bool,
+    },
+    /// List effective settings
+    List {
+        /// Show which file or the environment each value comes from
+        #[arg(long)]
+        show_origin: bool,
+    },
+}
+
+pub fn execute(action: ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Get { key } => get(&key),
+        ConfigAction::Set {
+            key,
+            value,
+            project,
+            system,
+        } => set(&key, &value, project, system),
+        ConfigAction::List { show_origin } => list(show_origin),
+    }
+}
+
+fn get(key: &str) -> Result<()> {
+    let mut merged = None;
+    for layer in config::config_layers()? {
+        if let Some(value) = config::get_key(&layer.table, key) {
+            merged = Some(value.clone());
+        }
+    }
+    match merged {
+        Some(value) => {
+            println!("{}", display_value(&value));
+            Ok(())
+        }
+        None => bail!("'{}' is not set", key),
+    }
+}
+
+fn set(key: &str, raw: &str, project: bool, system: bool) -> Result<()> {
+    let path = if project {
+        let top = key.split('.').next().unwrap_or(key);
+        if !config::PROJECT_KEYS.contains(&top) {
+            bail!(
+                "'{}' cannot be set per project (only {}); set it in the user config",
+                key,
+                config::PROJECT_KEYS.join(", ")
+            );
+        }
+        config::project_settings_path()
+    } else if system {
+        config::system_settings_path()
+    } else {
+        config::settings_path()
+    };
+    config::write_setting(&path, key, parse_value(raw))?;
+    ui::success(&format!("Set {} = {} in {}", key, raw, path.display()));
+    Ok(())
+}
+
+fn list(show_origin: bool) -> Result<()> {
+    // Later layers override earlier ones, key by key
+    let mut entries: Vec<(String, toml::Value, String)> = Vec::new();
+    for layer in config::config_layers()? {
+        let origin = layer.origin();
+        for (key, value) in config::flatten_table(&layer.table) {
+            match entries.iter_mut().find(|(k, _, _)| *k == key) {
+                Some(entry) => *entry = (key, value, origin.clone()),
+                None => entries.push((key, value, origin.clone())),
+            }
+        }
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if entries.is_empty() {
+        ui::info("No settings configured");
+        ui::hint(&format!(
+            "Set one with: agt config set <key> <value>  (writes {})",
+            config::settings_path().display()
+        ));
+        return Ok(());
+    }
+
+    let mut table = ui::table::new_table();
+    if show_origin {
+        table.set_header(["Key", "Value", "Origin"]);
+    } else {
+        table.set_header(["Key", "Value"]);
+    }
+    for (key, value, origin) in &entries {
+        let value = display_value(value);
+        if show_origin {
+            ui::table::add_row(&mut table, &[key, &value, origin]);
+        } else {
+            ui::table::add_row(&mut table, &[key, &value]);
+        }
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// A TOML value when `raw` is one (`true`, `3`, `["a"]`), else a string.
+fn parse_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("v = {}", raw))
+        .ok()
+        .and_then(|mut t| t.remove("v"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// Strings without quotes, everything else as TOML.
+fn display_value(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value("true"), toml::Value::Boolean(true));
+        assert_eq!(parse_value("opus"), toml::Value::String("opus".into()));
+        assert_eq!(parse_value("\"3\""), toml::Value::String("3".into()));
+        assert!(parse_value("[\"a\", \"b\"]").is_array());
+        assert_eq!(parse_value("~/skills"), toml::Value::String("~/skills".into()));
+    }
+}
@@ -0,0 +1,579 @@
+use crate::cmd::persona;
+use crate::{config, frontmatter, ui};
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Ok,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Ok => "ok",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+/// A safe repair `--fix` may apply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Fix {
+    /// Delete a dangling symlink
+    RemoveLink(PathBuf),
+    /// Drop settings.json hook handlers whose script is missing
+    PruneHooks,
+}
+
+#[derive(Debug)]
+struct Finding {
+    check: &'static str,
+    level: Level,
+    message: String,
+    path: Option<PathBuf>,
+    fix: Option<Fix>,
+    fixed: bool,
+}
+
+impl Finding {
+    fn new(check: &'static str, level: Level, message: String) -> Self {
+        Finding {
+            check,
+            level,
+            message,
+            path: None,
+            fix: None,
+            fixed: false,
+        }
+    }
+
+    fn at(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_path_buf());
+        self
+    }
+
+    fn fixable(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Run every check, optionally repair what is safe to repair, and report.
+pub fn execute(fix: bool, json: bool) -> Result<()> {
+    let mut findings = Vec::new();
+    check_source(&mut findings);
+    check_skills(&mut findings);
+    check_personas(&mut findings);
+    let settings = check_settings(&mut findings);
+    check_hooks(settings.as_ref(), &mut findings);
+
+    if fix {
+        apply_fixes(&mut findings);
+    }
+
+    if json {
+        let output: Vec<serde_json::Value> = findings
+            .iter()
+            .map(|f| {
+                serde_json::json!({
+                    "check": f.check,
+                    "level": f.level.name(),
+                    "message": f.message,
+                    "path": f.path.as_ref().map(|p| p.display().to_string()),
+                    "fixable": f.fix.is_some(),
+                    "fixed": f.fixed,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_findings(&findings, fix);
+    }
+
+    let problems = findings
+        .iter()
+        .filter(|f| f.level == Level::Error && !f.fixed)
+        .count();
+    if problems > 0 {
+        bail!("{} problem(s) found", problems);
+    }
+    Ok(())
+}
+
+// ── Checks ────────────────────────────────────────────────────────
+
+fn check_source(findings: &mut Vec<Finding>) {
+    let mut winner = None;
+    for candidate in config::source_dir_candidates() {
+        if candidate.is_valid() {
+            winner = Some(candidate);
+            break;
+        }
+        // Only explicit settings are worth a warning; discovery misses are normal
+        if candidate.is_explicit() {
+            findings.push(
+                Finding::new(
+                    "source",
+                    Level::Warn,
+                    format!("{} is set but is not a directory", candidate.origin),
+                )
+                .at(&candidate.path),
+            );
+        }
+    }
+
+    match winner {
+        Some(candidate) => findings.push(
+            Finding::new(
+                "source",
+                Level::Ok,
+                format!("Using skills library from {}", candidate.origin),
+            )
+            .at(&candidate.path),
+        ),
+        None => {
+            let message = match config::find_cwd_source_dir() {
+                Some(_) => "No skills library found; falling back to the current repository",
+                None => "No skills library found; set AGT_DIR or clone one to ~/.agent-skills",
+            };
+            findings.push(Finding::new("source", Level::Warn, message.to_string()));
+        }
+    }
+}
+
+fn check_skills(findings: &mut Vec<Finding>) {
+    let mut checked = 0;
+    for agent in <config::SkillAgent as clap::ValueEnum>::value_variants() {
+        let local_dir = config::skill_target(false, *agent);
+        let global_dir = config::skill_target(true, *agent);
+        let local = installed_items(&local_dir, true);
+        let global = installed_items(&global_dir, true);
+        checked += local.len() + global.len();
+
+        for (name, path) in local.iter().chain(&global) {
+            if is_dangling(path) {
+                findings.push(dangling("skills", name, path));
+                continue;
+            }
+            let skill_md = path.join("SKILL.md");
+            if !skill_md.is_file() {
+                findings.push(
+                    Finding::new("skills", Level::Warn, format!("'{}' has no SKILL.md", name))
+                        .at(path),
+                );
+            } else if let Some(problem) = frontmatter_problem(&skill_md, &["name", "description"]) {
+                findings.push(
+                    Finding::new("skills", Level::Error, format!("'{}': {}", name, problem))
+                        .at(&skill_md),
+                );
+            }
+        }
+
+        if same_dir(&local_dir, &global_dir) {
+            continue;
+        }
+        let global_names: HashSet<&str> = global.iter().map(|(name, _)| leaf(name)).collect();
+        for (name, path) in &local {
+            if global_names.contains(leaf(name)) {
+                findings.push(
+                    Finding::new(
+                        "skills",
+                        Level::Warn,
+                        format!(
+                            "'{}' is installed both locally and globally for {}; the local copy wins",
+                            leaf(name),
+                            agent
+                        ),
+                    )
+                    .at(path),
+                );
+            }
+        }
+    }
+
+    if !findings.iter().any(|f| f.check == "skills") {
+        findings.push(Finding::new(
+            "skills",
+            Level::Ok,
+            format!("{} installed skill(s) checked", checked),
+        ));
+    }
+}
+
+fn check_personas(findings: &mut Vec<Finding>) {
+    let mut checked = 0;
+    for dir in [config::local_persona_target(), config::global_persona_target()] {
+        for (name, path) in installed_items(&dir, false) {
+            checked += 1;
+            if is_dangling(&path) {
+                findings.push(dangling("personas", &name, &path));
+                continue;
+            }
+            let md = if path.is_file() {
+                Ok(path.clone())
+            } else {
+                persona::find_persona_md(&path)
+            };
+            match md {
+                Ok(md) => {
+                    if let Some(problem) = frontmatter_problem(&md, &["name"]) {
+                        let message = format!("'{}': {}", name, problem);
+                        findings.push(Finding::new("personas", Level::Error, message).at(&md));
+                    }
+                }
+                Err(e) => findings.push(
+                    Finding::new("personas", Level::Warn, format!("'{}': {:#}", name, e)).at(&path),
+                ),
+            }
+        }
+    }
+
+    if !findings.iter().any(|f| f.check == "personas") {
+        findings.push(Finding::new(
+            "personas",
+            Level::Ok,
+            format!("{} installed persona(s) checked", checked),
+        ));
+    }
+}
+
+/// Parse ~/.claude/settings.json; returns it when valid.
+fn check_settings(findings: &mut Vec<Finding>) -> Option<serde_json::Value> {
+    let path = config::claude_settings_path();
+    if !path.exists() {
+        let message = "No settings.json yet".to_string();
+        findings.push(Finding::new("settings", Level::Ok, message).at(&path));
+        return None;
+    }
+    let parsed = fs::read_to_string(&path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| Ok(serde_json::from_str::<serde_json::Value>(&content)?));
+    match parsed {
+        Ok(settings) => {
+            let message = "settings.json is valid".to_string();
+            findings.push(Finding::new("settings", Level::Ok, message).at(&path));
+            Some(settings)
+        }
+        Err(e) => {
+            findings.push(
+                Finding::new(
+                    "settings",
+                    Level::Error,
+                    format!("settings.json cannot be parsed: {:#}", e),
+                )
+                .at(&path),
+            );
+            None
+        }
+    }
+}
+
+fn check_hooks(settings: Option<&serde_json::Value>, findings: &mut Vec<Finding>) {
+    let hooks_target = config::global_hook_target();
+    let before = findings.len();
+
+    for (name, path) in installed_items(&hooks_target, false) {
+        if is_dangling(&path) {
+            findings.push(dangling("hooks", &name, &path));
+        }
+    }
+    if let Some(hooks) = settings.and_then(|s| s.get("hooks")) {
+        for (event, script) in missing_hook_scripts(hooks, &hooks_target) {
+            findings.push(
+                Finding::new(
+                    "hooks",
+                    Level::Error,
+                    format!("{} hook runs a missing script", event),
+                )
+                .at(&script)
+                .fixable(Fix::PruneHooks),
+            );
+        }
+    }
+
+    if findings.len() == before {
+        findings.push(Finding::new("hooks", Level::Ok, "Hook scripts are in place".to_string()));
+    }
+}
+
+// ── Helpers ───────────────────────────────────────────────────────
+
+/// Entries of an install directory as (name, path), including dangling
+/// symlinks. With `grouped`, one level of group directories is expanded.
+fn installed_items(dir: &Path, grouped: bool) -> Vec<(String, PathBuf)> {
+    let mut items = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return items;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        let is_group =
+            grouped && path.is_dir() && !path.is_symlink() && !path.join("SKILL.md").exists();
+        if is_group {
+            for (child, child_path) in installed_items(&path, false) {
+                items.push((format!("{}/{}", name, child), child_path));
+            }
+        } else {
+            items.push((name, path));
+        }
+    }
+    items.sort();
+    items
+}
+
+fn is_dangling(path: &Path) -> bool {
+    path.is_symlink() && !path.exists()
+}
+
+fn dangling(check: &'static str, name: &str, path: &Path) -> Finding {
+    let target = fs::read_link(path)
+        .map(|t| t.display().to_string())
+        .unwrap_or_default();
+    Finding::new(
+        check,
+        Level::Error,
+        format!("'{}' is a dangling symlink to {}", name, target),
+    )
+    .at(path)
+    .fixable(Fix::RemoveLink(path.to_path_buf()))
+}
+
+fn leaf(name: &str) -> &str {
+    name.rsplit('/').next().unwrap_or(name)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    matches!((fs::canonicalize(a), fs::canonicalize(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Why a markdown file's frontmatter is unusable, if it is.
+fn frontmatter_problem(path: &Path, required: &[&str]) -> Option<String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return Some(format!("cannot read {}: {}", path.display(), e)),
+    };
+    if !content.trim_start().starts_with("---") {
+        return Some("missing frontmatter".to_string());
+    }
+    let fm = match frontmatter::parse(&content) {
+        Ok((fm, _)) => fm,
+        Err(e) => return Some(format!("invalid frontmatter: {:#}", e)),
+    };
+    let missing: Vec<&str> = required
+        .iter()
+        .copied()
+        .filter(|field| match *field {
+            "name" => fm.name.as_deref().is_none_or(str::is_empty),
+            "description" => fm.description.as_deref().is_none_or(str::is_empty),
+            _ => false,
+        })
+        .collect();
+    if missing.is_empty() {
+        None
+    } else {
+        Some(format!("frontmatter is missing {}", missing.join(", ")))
+    }
+}
+
+/// Script path of a command hook handler under `hooks_target`, if any.
+fn hook_script(command: &str, hooks_target: &Path) -> Option<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+    command.split_whitespace().find_map(|token| {
+        let token = token.trim_matches(|c| c == '"' || c == '\'');
+        let path = match token
+            .strip_prefix("~/")
+            .or_else(|| token.strip_prefix("$HOME/"))
+        {
+            Some(rest) => home.join(rest),
+            None => PathBuf::from(token),
+        };
+        path.starts_with(hooks_target).then_some(path)
+    })
+}
+
+/// (event, script) for every command handler whose script is missing.
+fn missing_hook_scripts(hooks: &serde_json::Value, hooks_target: &Path) -> Vec<(String, PathBuf)> {
+    let mut missing = Vec::new();
+    let Some(events) = hooks.as_object() else {
+        return missing;
+    };
+    for (event, entries) in events {
+        for handler in handlers(entries) {
+            let script = handler
+                .get("command")
+                .and_then(|v| v.as_str())
+                .and_then(|cmd| hook_script(cmd, hooks_target));
+            if let Some(script) = script.filter(|s| !s.exists()) {
+                missing.push((event.clone(), script));
+            }
+        }
+    }
+    missing
+}
+
+fn handlers(entries: &serde_json::Value) -> impl Iterator<Item = &serde_json::Value> {
+    entries
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("hooks").and_then(|v| v.as_array()))
+        .flatten()
+}
+
+/// Remove handlers with missing scripts, then empty matchers and events.
+/// Returns the number of handlers removed.
+fn prune_missing_hooks(settings: &mut serde_json::Value, hooks_target: &Path) -> usize {
+    let mut removed = 0;
+    let Some(events) = settings.get_mut("hooks").and_then(|v| v.as_object_mut()) else {
+        return 0;
+    };
+    for entries in events.values_mut() {
+        let Some(entries) = entries.as_array_mut() else {
+            continue;
+        };
+        for entry in entries.iter_mut() {
+            if let Some(hooks) = entry.get_mut("hooks").and_then(|v| v.as_array_mut()) {
+                let before = hooks.len();
+                hooks.retain(|h| {
+                    let script = h
+                        .get("command")
+                        .and_then(|v| v.as_str())
+                        .and_then(|cmd| hook_script(cmd, hooks_target));
+                    script.is_none_or(|s| s.exists())
+                });
+                removed += before - hooks.len();
+            }
+        }
+        entries.retain(|entry| {
+            entry
+                .get("hooks")
+                .and_then(|v| v.as_array())
+                .is_none_or(|hooks| !hooks.is_empty())
+        });
+    }
+    events.retain(|_, entries| entries.as_array().is_none_or(|a| !a.is_empty()));
+    if events.is_empty() {
+        if let Some(settings) = settings.as_object_mut() {
+            settings.remove("hooks");
+        }
+    }
+    removed
+}
+
+fn apply_fixes(findings: &mut [Finding]) {
+    let mut hooks_pruned = None;
+    for finding in findings.iter_mut() {
+        let result = match &finding.fix {
+            None => continue,
+            Some(Fix::RemoveLink(path)) => fs::remove_file(path).map_err(anyhow::Error::from),
+            Some(Fix::PruneHooks) => hooks_pruned
+                .get_or_insert_with(prune_settings_hooks)
+                .as_ref()
+                .map(|_| ())
+                .map_err(|e| anyhow::anyhow!("{:#}", e)),
+        };
+        match result {
+            Ok(()) => finding.fixed = true,
+            Err(e) => ui::warn(&format!("Could not fix '{}': {:#}", finding.message, e)),
+        }
+    }
+}
+
+fn prune_settings_hooks() -> Result<usize> {
+    let path = config::claude_settings_path();
+    let mut settings: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    let removed = prune_missing_hooks(&mut settings, &config::global_hook_target());
+    fs::write(&path, serde_json::to_string_pretty(&settings)?)?;
+    Ok(removed)
+}
+
+fn print_findings(findings: &[Finding], fix: bool) {
+    let mut current = "";
+    for finding in findings {
+        if finding.check != current {
+            current = finding.check;
+            ui::section(&format!("Doctor: {}", current));
+        }
+        let mut message = finding.message.clone();
+        if let Some(path) = &finding.path {
+            message = format!("{} ({})", message, path.display());
+        }
+        match (finding.level, finding.fixed) {
+            (_, true) => ui::success(&format!("Fixed: {}", message)),
+            (Level::Ok, _) => ui::success(&message),
+            (Level::Warn, _) => ui::warn(&message),
+            (Level::Error, _) => ui::error(&message),
+        }
+    }
+
+    let fixable = findings.iter().filter(|f| f.fix.is_some() && !f.fixed).count();
+    if !fix && fixable > 0 {
+        eprintln!();
+        ui::hint(&format!("{} problem(s) can be repaired with: agt doctor --fix", fixable));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_missing_hook_scripts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let hooks_target = tmp.path().join("hooks");
+        fs::create_dir_all(&hooks_target).unwrap();
+        fs::write(hooks_target.join("present.sh"), "").unwrap();
+
+        let present = format!("bash {}/present.sh", hooks_target.display());
+        let gone = format!("bash {}/gone.sh", hooks_target.display());
+        let mut settings = serde_json::json!({
+            "hooks": {
+                "Stop": [{ "hooks": [
+                    { "type": "command", "command": present },
+                    { "type": "command", "command": gone },
+                ]}],
+                "PreToolUse": [{ "matcher": "Bash", "hooks": [
+                    { "type": "command", "command": gone },
+                ]}],
+                "Notification": [{ "hooks": [{ "type": "command", "command": "notify-send hi" }] }],
+            }
+        });
+
+        let missing = missing_hook_scripts(&settings["hooks"], &hooks_target);
+        assert_eq!(missing.len(), 2);
+        assert!(missing.iter().all(|(_, script)| script.ends_with("gone.sh")));
+
+        assert_eq!(prune_missing_hooks(&mut settings, &hooks_target), 2);
+        assert_eq!(settings["hooks"]["Stop"][0]["hooks"].as_array().unwrap().len(), 1);
+        assert!(settings["hooks"].get("PreToolUse").is_none());
+        assert!(settings["hooks"].get("Notification").is_some());
+    }
+
+    #[test]
+    fn test_frontmatter_problem() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("SKILL.md");
+        fs::write(&path, "---\nname: ok\ndescription: fine\n---\nbody\n").unwrap();
+        assert!(frontmatter_problem(&path, &["name", "description"]).is_none());
+
+        fs::write(&path, "---\nname: ok\n---\n").unwrap();
+        assert!(frontmatter_problem(&path, &["name", "description"])
+            .unwrap()
+            .contains("description"));
+
+        fs::write(&path, "---\nname: [unclosed\n---\n").unwrap();
+        assert!(frontmatter_problem(&path, &["name"]).unwrap().contains("invalid"));
+        fs::write(&path, "# no frontmatter\n").unwrap();
+        assert!(frontmatter_problem(&path, &["name"]).is_some());
+    }
+}
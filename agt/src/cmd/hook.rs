@@ -150,7 +150,7 @@ fn list(json_output: bool) -> Result<()> {
     ui::section("Hooks");
 
     let mut table = ui::table::new_table();
-    table.set_header(&["Status", "Type", "Name", "Event", "Description"]);
+    table.set_header(["Status", "Type", "Name", "Event", "Description"]);
     for (name, def) in &registry {
         let is_installed = is_hook_installed(name, def, &installed);
         let icon = if is_installed {
@@ -192,7 +192,7 @@ fn show(name: &str) -> Result<()> {
     ui::section(name);
 
     let mut table = ui::table::new_table();
-    table.set_header(&["Property", "Value"]);
+    table.set_header(["Property", "Value"]);
     ui::table::add_row(&mut table, &["Type", &def.hook_type.to_string()]);
     ui::table::add_row(&mut table, &["Event", &def.event]);
     ui::table::add_row(&mut table, &["Description", &def.description]);
@@ -308,6 +308,20 @@ fn install(name: Option<String>, force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Install a single registry hook (used by `agt sync`).
+pub fn install_named(name: &str) -> Result<()> {
+    install(Some(name.to_string()), false)
+}
+
+/// Whether a registry hook is registered in settings.json.
+pub fn is_installed(name: &str) -> Result<bool> {
+    let registry = load_registry()?;
+    let def = registry
+        .get(name)
+        .with_context(|| format!("Hook '{}' not found in registry", name))?;
+    Ok(is_hook_installed(name, def, &load_installed_hooks()?))
+}
+
 // ── Uninstall ─────────────────────────────────────────────────────
 
 fn uninstall(name: Option<String>) -> Result<()> {
@@ -547,7 +561,7 @@ fn which_exists(cmd: &str) -> bool {
 fn hooks_source_dir() -> Result<PathBuf> {
     let source = config::find_source_dir()
         .or_else(config::find_cwd_source_dir)
-        .with_context(|| config::source_dir_hint())?;
+        .with_context(config::source_dir_hint)?;
     Ok(source.join("hooks"))
 }
 
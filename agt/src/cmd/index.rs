@@ -0,0 +1,64 @@
+use crate::{config, remote, ui};
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum IndexAction {
+    /// Write index.json for a skills library, for publishing with it
+    Build {
+        /// Library root (defaults to the current skills repository)
+        dir: Option<PathBuf>,
+        /// Write here instead of <dir>/index.json ("-" for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Only check that the existing index is up to date (for CI)
+        #[arg(long, conflicts_with = "output")]
+        check: bool,
+    },
+}
+
+pub fn execute(action: IndexAction) -> Result<()> {
+    match action {
+        IndexAction::Build { dir, output, check } => build(dir, output, check),
+    }
+}
+
+fn build(dir: Option<PathBuf>, output: Option<PathBuf>, check: bool) -> Result<()> {
+    let source_dir = match dir {
+        Some(dir) => dir,
+        None => config::find_cwd_source_dir()
+            .or_else(config::find_source_dir)
+            .context(config::source_dir_hint())?,
+    };
+    if !config::has_skill_groups(&source_dir) {
+        bail!("No skill groups (group/skill/SKILL.md) in {}", source_dir.display());
+    }
+
+    let index = remote::build_index(&source_dir)?;
+    let json = format!("{}\n", serde_json::to_string_pretty(&index)?);
+    let path = output.unwrap_or_else(|| source_dir.join(remote::INDEX_FILE));
+
+    if check {
+        let current = fs::read(&path).ok().and_then(|c| remote::parse_index(&c).ok());
+        if current.as_ref() != Some(&index) {
+            bail!("{} is missing or out of date. Run: agt index build", path.display());
+        }
+        ui::success(&format!("{} is up to date", path.display()));
+        return Ok(());
+    }
+
+    if path.as_os_str() == "-" {
+        print!("{}", json);
+        return Ok(());
+    }
+    fs::write(&path, json).context(format!("Failed to write {}", path.display()))?;
+    ui::success(&format!(
+        "Indexed {} skills and {} profiles in {}",
+        index.skills.len(),
+        index.profiles.len(),
+        path.display()
+    ));
+    Ok(())
+}
@@ -0,0 +1,463 @@
+use crate::{config, frontmatter};
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Claude rejects skill descriptions longer than this.
+const MAX_DESCRIPTION: usize = 1024;
+/// Longest accepted skill name.
+const MAX_NAME: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One lint finding, located at `file:line`.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub file: PathBuf,
+    pub line: usize,
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(file: &Path, line: usize, code: &'static str, message: String) -> Self {
+        Diagnostic {
+            file: file.to_path_buf(),
+            line,
+            severity: Severity::Error,
+            code,
+            message,
+        }
+    }
+
+    fn warning(file: &Path, line: usize, code: &'static str, message: String) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Self::error(file, line, code, message)
+        }
+    }
+}
+
+/// Lint a skill directory or a whole library (default: the current
+/// directory if it is either, else the configured source library).
+pub fn execute(path: Option<PathBuf>, json: bool) -> Result<()> {
+    let root = match path {
+        Some(path) => path,
+        None => {
+            let cwd = PathBuf::from(".");
+            if cwd.join("SKILL.md").exists() || config::has_skill_groups(&cwd) {
+                cwd
+            } else {
+                config::find_source_dir().context(config::source_dir_hint())?
+            }
+        }
+    };
+    if !root.is_dir() {
+        bail!("Not a directory: {}", root.display());
+    }
+
+    let (checked, diagnostics) = if config::has_skill_groups(&root) {
+        lint_library(&root)
+    } else {
+        (1, lint_skill(&root))
+    };
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+
+    if json {
+        let output: Vec<serde_json::Value> = diagnostics
+            .iter()
+            .map(|d| {
+                serde_json::json!({
+                    "file": display_path(&d.file),
+                    "line": d.line,
+                    "severity": match d.severity {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                    },
+                    "code": d.code,
+                    "message": d.message,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        for d in &diagnostics {
+            let label = match d.severity {
+                Severity::Error => format!("error[{}]", d.code).red().bold(),
+                Severity::Warning => format!("warning[{}]", d.code).yellow().bold(),
+            };
+            println!("{}:{}: {}: {}", display_path(&d.file), d.line, label, d.message);
+        }
+        crate::ui::info(&format!(
+            "Linted {} skill(s): {} error(s), {} warning(s)",
+            checked, errors, warnings
+        ));
+    }
+
+    if errors > 0 {
+        bail!("Lint failed with {} error(s)", errors);
+    }
+    Ok(())
+}
+
+/// Lint every skill in a library, plus names shared across groups.
+/// Returns the number of skills checked and the diagnostics.
+pub fn lint_library(root: &Path) -> (usize, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let mut by_name: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    let mut checked = 0;
+
+    for group in config::skill_groups(root) {
+        for name in config::skills_in_group(root, &group) {
+            let dir = root.join(&group).join(&name);
+            diagnostics.extend(lint_skill(&dir));
+            by_name.entry(name).or_default().push(dir.join("SKILL.md"));
+            checked += 1;
+        }
+    }
+
+    for (name, files) in &by_name {
+        if files.len() < 2 {
+            continue;
+        }
+        for file in files {
+            let others: Vec<String> = files
+                .iter()
+                .filter(|f| *f != file)
+                .map(|f| display_path(f))
+                .collect();
+            diagnostics.push(Diagnostic::error(
+                file,
+                1,
+                "duplicate-name",
+                format!("skill name '{}' is also used by {}", name, others.join(", ")),
+            ));
+        }
+    }
+    (checked, diagnostics)
+}
+
+/// Lint one skill directory.
+pub fn lint_skill(dir: &Path) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let skill_md = dir.join("SKILL.md");
+    let content = match fs::read_to_string(&skill_md) {
+        Ok(content) => content,
+        Err(_) => {
+            diagnostics.push(Diagnostic::error(
+                &skill_md,
+                1,
+                "missing-skill-md",
+                "skill directory has no readable SKILL.md".to_string(),
+            ));
+            return diagnostics;
+        }
+    };
+
+    let dir_name = dir
+        .canonicalize()
+        .ok()
+        .and_then(|d| d.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    let body_start = check_frontmatter(&skill_md, &content, &dir_name, &mut diagnostics);
+    check_references(dir, &skill_md, &content, body_start, &mut diagnostics);
+    check_scripts(dir, &mut diagnostics);
+    diagnostics
+}
+
+/// Validate the frontmatter block. Returns the 0-based index of the first
+/// body line.
+fn check_frontmatter(
+    file: &Path,
+    content: &str,
+    dir_name: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> usize {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.first().map(|l| l.trim_end()) != Some("---") {
+        diagnostics.push(Diagnostic::error(
+            file,
+            1,
+            "frontmatter",
+            "SKILL.md must start with a `---` frontmatter block".to_string(),
+        ));
+        return 0;
+    }
+    let Some(close) = lines.iter().skip(1).position(|l| l.trim_end() == "---") else {
+        diagnostics.push(Diagnostic::error(
+            file,
+            1,
+            "frontmatter",
+            "frontmatter has no closing `---`".to_string(),
+        ));
+        return 0;
+    };
+    let close = close + 1;
+    let yaml = lines[1..close].join("\n");
+    // 1-based file line of a frontmatter key, or the opening `---`
+    let key_line = |key: &str| {
+        lines[1..close]
+            .iter()
+            .position(|l| l.starts_with(&format!("{}:", key)))
+            .map_or(1, |i| i + 2)
+    };
+
+    let fm: frontmatter::Frontmatter = match serde_yaml::from_str(&yaml) {
+        Ok(fm) => fm,
+        Err(e) => {
+            let line = e.location().map_or(1, |loc| loc.line() + 1);
+            diagnostics.push(Diagnostic::error(
+                file,
+                line,
+                "frontmatter",
+                format!("invalid frontmatter: {}", e),
+            ));
+            return close + 1;
+        }
+    };
+
+    match fm.name.as_deref().map(str::trim) {
+        None | Some("") => diagnostics.push(Diagnostic::error(
+            file,
+            1,
+            "name",
+            "frontmatter is missing `name`".to_string(),
+        )),
+        Some(name) => {
+            if name != dir_name {
+                diagnostics.push(Diagnostic::error(
+                    file,
+                    key_line("name"),
+                    "name",
+                    format!("name '{}' does not match directory '{}'", name, dir_name),
+                ));
+            }
+            let valid_chars = name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+            if !valid_chars || name.len() > MAX_NAME {
+                diagnostics.push(Diagnostic::warning(
+                    file,
+                    key_line("name"),
+                    "name",
+                    format!(
+                        "name '{}' should use lowercase letters, digits and hyphens (max {})",
+                        name, MAX_NAME
+                    ),
+                ));
+            }
+        }
+    }
+
+    match fm.description.as_deref().map(str::trim) {
+        None | Some("") => diagnostics.push(Diagnostic::error(
+            file,
+            1,
+            "description",
+            "frontmatter is missing `description`".to_string(),
+        )),
+        Some(desc) if desc.chars().count() > MAX_DESCRIPTION => {
+            diagnostics.push(Diagnostic::error(
+                file,
+                key_line("description"),
+                "description",
+                format!(
+                    "description is {} characters; the limit is {}",
+                    desc.chars().count(),
+                    MAX_DESCRIPTION
+                ),
+            ))
+        }
+        Some(_) => {}
+    }
+
+    if let Some(version) = &fm.version {
+        if crate::remote::parse_version(version).is_none() {
+            diagnostics.push(Diagnostic::error(
+                file,
+                key_line("version"),
+                "version",
+                format!("version '{}' is not a semver version", version),
+            ));
+        }
+    }
+
+    for (key, values) in [
+        ("tags", &fm.tags),
+        ("trigger-keywords", &fm.trigger_keywords),
+    ] {
+        if values.as_ref().is_some_and(|v| v.iter().any(|s| s.trim().is_empty())) {
+            diagnostics.push(Diagnostic::warning(
+                file,
+                key_line(key),
+                "keywords",
+                format!("`{}` contains an empty entry", key),
+            ));
+        }
+    }
+
+    close + 1
+}
+
+/// Flag relative markdown links and `scripts/`, `references/` or `assets/`
+/// paths in the body that do not exist.
+fn check_references(
+    dir: &Path,
+    file: &Path,
+    content: &str,
+    body_start: usize,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (i, line) in content.lines().enumerate().skip(body_start) {
+        for reference in referenced_paths(line) {
+            if !dir.join(&reference).exists() {
+                diagnostics.push(Diagnostic::error(
+                    file,
+                    i + 1,
+                    "missing-file",
+                    format!("referenced file '{}' does not exist", reference),
+                ));
+            }
+        }
+    }
+}
+
+/// Relative paths referenced on one line of markdown.
+fn referenced_paths(line: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    // [text](target) links
+    let mut rest = line;
+    while let Some(start) = rest.find("](") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find(')') else {
+            break;
+        };
+        let target = rest[..end].split_whitespace().next().unwrap_or("");
+        let target = target.split('#').next().unwrap_or("");
+        let external = target.contains("://") || target.starts_with("mailto:");
+        if !target.is_empty() && !external && !target.starts_with('/') {
+            paths.push(target.to_string());
+        }
+        rest = &rest[end..];
+    }
+
+    // `scripts/...` style inline code
+    for (i, chunk) in line.split('`').enumerate() {
+        let candidate = chunk.split_whitespace().next().unwrap_or("");
+        let bundled = ["scripts/", "references/", "assets/"]
+            .iter()
+            .any(|prefix| candidate.starts_with(prefix));
+        if i % 2 == 1 && bundled && !paths.iter().any(|p| p == candidate) {
+            paths.push(candidate.to_string());
+        }
+    }
+    paths
+}
+
+/// Scripts with a shebang must be executable.
+fn check_scripts(dir: &Path, diagnostics: &mut Vec<Diagnostic>) {
+    let scripts = dir.join("scripts");
+    let Ok(files) = crate::util::list_files(&scripts) else {
+        return;
+    };
+    for (_, path) in files {
+        let has_shebang = fs::read(&path).is_ok_and(|data| data.starts_with(b"#!"));
+        let executable = fs::metadata(&path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0);
+        if has_shebang && !executable {
+            diagnostics.push(Diagnostic::error(
+                &path,
+                1,
+                "not-executable",
+                "script has a shebang but is not executable (chmod +x)".to_string(),
+            ));
+        }
+    }
+}
+
+fn display_path(path: &Path) -> String {
+    let path = path.strip_prefix(".").unwrap_or(path);
+    path.display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(dir: &Path, frontmatter: &str, body: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), format!("---\n{}---\n{}", frontmatter, body)).unwrap();
+    }
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<&'static str> {
+        diagnostics.iter().map(|d| d.code).collect()
+    }
+
+    #[test]
+    fn test_lint_clean_skill() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("dev/git-helper");
+        write_skill(
+            &dir,
+            "name: git-helper\ndescription: Helps with git\ntags: [git]\n",
+            "Run `scripts/run.sh` or see [notes](references/notes.md#usage).\n",
+        );
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::create_dir_all(dir.join("references")).unwrap();
+        fs::write(dir.join("references/notes.md"), "").unwrap();
+        fs::write(dir.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        fs::set_permissions(dir.join("scripts/run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert!(lint_skill(&dir).is_empty());
+    }
+
+    #[test]
+    fn test_lint_reports_problems_with_lines() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("dev/git-helper");
+        write_skill(
+            &dir,
+            "name: Git_Helper\ndescription: \"\"\nversion: latest\n",
+            "\nSee [docs](references/missing.md) and https://example.com.\n",
+        );
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(dir.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        fs::set_permissions(dir.join("scripts/run.sh"), fs::Permissions::from_mode(0o644)).unwrap();
+
+        let diagnostics = lint_skill(&dir);
+        assert_eq!(
+            codes(&diagnostics),
+            ["name", "name", "description", "version", "missing-file", "not-executable"]
+        );
+        assert_eq!(diagnostics[0].line, 2);
+        assert_eq!(diagnostics[3].line, 4);
+        assert_eq!(diagnostics[4].line, 7);
+    }
+
+    #[test]
+    fn test_lint_invalid_yaml_and_duplicates() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_skill(&tmp.path().join("a/tool"), "name: tool\ndescription: x\n", "");
+        write_skill(&tmp.path().join("b/tool"), "name: tool\ndescription: [x\n", "");
+
+        let (checked, diagnostics) = lint_library(tmp.path());
+        assert_eq!(checked, 2);
+        assert_eq!(
+            codes(&diagnostics),
+            ["frontmatter", "duplicate-name", "duplicate-name"]
+        );
+    }
+}
@@ -1,5 +1,15 @@
+pub mod cache;
+pub mod config;
+pub mod doctor;
 pub mod hook;
+pub mod index;
+pub mod lint;
+pub mod outdated;
 pub mod persona;
+pub mod profile;
 pub mod run;
+pub mod search;
 pub mod skill;
+pub mod source;
+pub mod sync;
 pub mod team;
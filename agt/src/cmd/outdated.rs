@@ -0,0 +1,197 @@
+use crate::{remote, ui, util};
+use anyhow::Result;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A remote-installed item to check: (display name, scope, installed path)
+pub type OutdatedTarget = (String, String, PathBuf);
+
+/// Compare each item's recorded commit against its upstream ref and print
+/// a table (or JSON). Only performs a ref lookup per repo@ref — no downloads.
+pub fn report(targets: &[OutdatedTarget], kind: &str, json: bool) -> Result<()> {
+    // Skills from the same repo and ref share a single lookup
+    let mut upstream: HashMap<String, std::result::Result<(String, String), String>> =
+        HashMap::new();
+    let mut entries: Vec<serde_json::Value> = Vec::new();
+
+    for (name, scope, path) in targets {
+        let spec = match remote::parse_metadata(path) {
+            Ok(spec) => spec,
+            Err(e) => {
+                ui::warn(&format!("Skipping '{}': {:#}", name, e));
+                continue;
+            }
+        };
+
+        // Local directories and bundles have no upstream ref to compare against
+        if spec.transport.local_path().is_some() {
+            entries.push(serde_json::json!({
+                "name": name,
+                "scope": scope,
+                "source": spec.to_string(),
+                "ref": null,
+                "current": null,
+                "latest": null,
+                "status": "local",
+                "error": null,
+            }));
+            continue;
+        }
+
+        // Constraints may resolve to skill-scoped tags, so they are per path
+        let key = match &spec.version_req {
+            Some(req) => format!("{}/{}@{}", spec.repo_label(), spec.path, req),
+            None => format!("{}@{}", spec.repo_label(), spec.git_ref),
+        };
+        let latest = upstream
+            .entry(key)
+            .or_insert_with(|| {
+                let mut unpinned = remote::RemoteSpec {
+                    commit: None,
+                    ..spec.clone()
+                };
+                remote::resolve_version(&mut unpinned)
+                    .and_then(|_| remote::resolve_commit(&unpinned))
+                    .map(|commit| (unpinned.git_ref, commit))
+                    .map_err(|e| format!("{:#}", e))
+            })
+            .clone();
+
+        let status = match &latest {
+            Ok(latest) => compare(&spec, latest),
+            Err(_) => "error",
+        };
+
+        entries.push(serde_json::json!({
+            "name": name,
+            "scope": scope,
+            "source": spec.to_string(),
+            "ref": spec.git_ref,
+            "current": spec.commit,
+            "latest": latest.as_ref().ok().map(|(_, commit)| commit),
+            "status": status,
+            "error": latest.as_ref().err(),
+        }));
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        ui::info(&format!("No remote-installed {}s found.", kind));
+        return Ok(());
+    }
+
+    let mut table = ui::table::new_table();
+    table.set_header(["Name", "Scope", "Ref", "Current", "Latest", "Status"]);
+    let mut outdated = 0usize;
+    for entry in &entries {
+        let short = |field: &str| {
+            entry[field]
+                .as_str()
+                .map(|c| remote::short_commit(c).to_string())
+                .unwrap_or_else(|| "-".to_string())
+        };
+        let status = match entry["status"].as_str().unwrap_or("") {
+            "up-to-date" => "up to date".green().to_string(),
+            "outdated" => {
+                outdated += 1;
+                "outdated".yellow().bold().to_string()
+            }
+            "unknown" => "unknown".dimmed().to_string(),
+            "local" => "local source".dimmed().to_string(),
+            _ => "error".red().to_string(),
+        };
+        ui::table::add_row(&mut table, &[
+            entry["name"].as_str().unwrap_or(""),
+            entry["scope"].as_str().unwrap_or(""),
+            entry["ref"].as_str().unwrap_or(""),
+            &short("current"),
+            &short("latest"),
+            &status,
+        ]);
+    }
+    println!("{table}");
+
+    let mut failed = 0usize;
+    for entry in &entries {
+        if let Some(err) = entry["error"].as_str() {
+            ui::warn(&format!("{}: {}", entry["name"].as_str().unwrap_or(""), err));
+            failed += 1;
+        }
+    }
+    if outdated > 0 {
+        ui::hint(&format!("{} {}(s) can be updated", outdated, kind));
+    } else if failed == 0 {
+        ui::success(&format!("All {}s are up to date", kind));
+    }
+    Ok(())
+}
+
+/// Status of an install against the upstream (ref, commit) it resolves to.
+/// Without a recorded commit the installed ref is compared instead: a commit
+/// SHA directly, and a constraint's tag by whether it still resolves there.
+fn compare(
+    installed: &remote::RemoteSpec,
+    (latest_ref, latest_commit): &(String, String),
+) -> &'static str {
+    let current = match &installed.commit {
+        Some(commit) => commit,
+        None if util::is_commit_sha(&installed.git_ref) => &installed.git_ref,
+        None if installed.version_req.is_some() => {
+            return if installed.git_ref == *latest_ref {
+                "up-to-date"
+            } else {
+                "outdated"
+            };
+        }
+        // A branch may have moved since; nothing recorded tells how far
+        None => return "unknown",
+    };
+    if current.eq_ignore_ascii_case(latest_commit) {
+        "up-to-date"
+    } else {
+        "outdated"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "0123456789abcdef0123456789abcdef01234567";
+    const NEW: &str = "89abcdef0123456789abcdef0123456789abcdef";
+
+    fn installed(
+        git_ref: &str,
+        commit: Option<&str>,
+        version_req: Option<&str>,
+    ) -> remote::RemoteSpec {
+        remote::RemoteSpec {
+            git_ref: git_ref.to_string(),
+            commit: commit.map(str::to_string),
+            version_req: version_req.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compare_recorded_commit() {
+        let latest = ("main".to_string(), NEW.to_string());
+        assert_eq!(compare(&installed("main", Some(NEW), None), &latest), "up-to-date");
+        assert_eq!(compare(&installed("main", Some(OLD), None), &latest), "outdated");
+    }
+
+    #[test]
+    fn test_compare_falls_back_to_ref() {
+        let latest = ("v1.3.0".to_string(), NEW.to_string());
+        assert_eq!(compare(&installed(OLD, None, None), &latest), "outdated");
+        assert_eq!(compare(&installed(&NEW.to_uppercase(), None, None), &latest), "up-to-date");
+        assert_eq!(compare(&installed("v1.2.0", None, Some("^1")), &latest), "outdated");
+        assert_eq!(compare(&installed("v1.3.0", None, Some("^1")), &latest), "up-to-date");
+        assert_eq!(compare(&installed("main", None, None), &latest), "unknown");
+    }
+}
@@ -82,10 +82,11 @@ fn suggest_static_index() {
                     let target = config::global_skill_target().join("context");
                     let _ = fs::create_dir_all(&target);
                     let link_path = target.join("static-index");
-                    if !link_path.exists() && !link_path.is_symlink() {
-                        if symlink(&skill_path, &link_path).is_ok() {
-                            ui::success("Installed skill 'context/static-index' (global)");
-                        }
+                    if !link_path.exists()
+                        && !link_path.is_symlink()
+                        && symlink(&skill_path, &link_path).is_ok()
+                    {
+                        ui::success("Installed skill 'context/static-index' (global)");
                     }
                 } else {
                     ui::warn("static-index skill not found in source library");
@@ -98,7 +99,7 @@ fn suggest_static_index() {
 }
 
 /// Post-install actions: refresh index and suggest static-index
-fn post_persona_install() {
+pub fn post_persona_install() {
     refresh_static_index();
     suggest_static_index();
 }
@@ -118,9 +119,13 @@ pub enum PersonaAction {
         /// Install all library personas
         #[arg(short, long)]
         all: bool,
-        /// Remote spec: owner/repo/path[@ref]
+        /// Remote spec: [host/]owner/repo/path[@ref], a local directory, file:// URL or .tar.gz
+        /// (defaults to the `source` setting)
         #[arg(long, value_name = "SPEC")]
         from: Option<String>,
+        /// Reinstall exactly the personas recorded in the project's agt.lock
+        #[arg(long, conflicts_with_all = ["name", "global", "all", "from"])]
+        locked: bool,
     },
     /// Uninstall a persona
     Uninstall {
@@ -133,6 +138,14 @@ pub enum PersonaAction {
         #[arg(short, long)]
         all: bool,
     },
+    /// Restore the version replaced by the last install
+    Rollback {
+        /// Persona name
+        name: String,
+        /// Roll back the global install
+        #[arg(short, long)]
+        global: bool,
+    },
     /// List available and installed personas
     List {
         /// Show only installed
@@ -148,6 +161,18 @@ pub enum PersonaAction {
         #[arg(long)]
         json: bool,
     },
+    /// Show remote-installed personas whose upstream ref has moved (no downloads)
+    Outdated {
+        /// Check only global personas
+        #[arg(short, long)]
+        global: bool,
+        /// Check only local personas
+        #[arg(short, long)]
+        local: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Create a new persona (template or AI-generated)
     Create {
         /// Persona name
@@ -218,7 +243,15 @@ pub fn execute(action: PersonaAction) -> Result<()> {
             force,
             all,
             from,
-        } => install(name, global, force, all, from),
+            locked,
+        } => {
+            if locked {
+                install_locked()
+            } else {
+                let from = from.or_else(|| fallback_source(name.as_deref()));
+                install(name, global, force, all, from)
+            }
+        }
         PersonaAction::Uninstall { name, global, all } => {
             if all {
                 uninstall_all(global)
@@ -227,12 +260,18 @@ pub fn execute(action: PersonaAction) -> Result<()> {
                 uninstall(&name, global)
             }
         }
+        PersonaAction::Rollback { name, global } => rollback(&name, global),
         PersonaAction::List {
             installed,
             local,
             global,
             json,
         } => list(installed, local, global, json),
+        PersonaAction::Outdated {
+            global,
+            local,
+            json,
+        } => outdated(global, local, json),
         PersonaAction::Create {
             name,
             ai,
@@ -264,6 +303,23 @@ pub fn execute(action: PersonaAction) -> Result<()> {
     }
 }
 
+/// The `source` setting, when the local library lacks the persona (or, for
+/// `--all`, there is no local library); an explicit `--from` always wins.
+fn fallback_source(name: Option<&str>) -> Option<String> {
+    let source = config::default_source()?;
+    let library = config::find_source_dir()
+        .or_else(config::find_cwd_source_dir)
+        .map(|dir| config::persona_library(&dir));
+    let local = match (library, name) {
+        (Some(library), Some(name)) => {
+            util::validate_name(name).is_ok() && find_in_library(&library, name).is_ok()
+        }
+        (Some(library), None) => library.is_dir(),
+        (None, _) => false,
+    };
+    (!local).then_some(source)
+}
+
 fn install(
     name: Option<String>,
     global: bool,
@@ -298,9 +354,7 @@ fn install(
     fs::create_dir_all(&target_dir)?;
     let link_path = target_dir.join(&name);
 
-    util::ensure_target_clear(&link_path, force, &name)?;
-
-    symlink(&persona_path, &link_path)?;
+    util::install_atomic(&link_path, force, &name, |staging| Ok(symlink(&persona_path, staging)?))?;
 
     let scope = if global { "global" } else { "local" };
     ui::success(&format!("Installed persona '{}' ({})", name, scope));
@@ -309,7 +363,7 @@ fn install(
 }
 
 /// Find a persona in the library — handles both directories and .md files
-fn find_in_library(persona_lib: &Path, name: &str) -> Result<PathBuf> {
+pub fn find_in_library(persona_lib: &Path, name: &str) -> Result<PathBuf> {
     // Check exact directory
     let as_dir = persona_lib.join(name);
     if as_dir.is_dir() {
@@ -365,20 +419,12 @@ fn install_all(persona_lib: &Path, global: bool, force: bool) -> Result<()> {
         let name = raw_name.strip_suffix(".md").unwrap_or(&raw_name).to_string();
         let link_path = target_dir.join(&name);
 
-        if link_path.exists() || link_path.is_symlink() {
-            if force {
-                if link_path.is_symlink() || link_path.is_file() {
-                    fs::remove_file(&link_path)?;
-                } else {
-                    fs::remove_dir_all(&link_path)?;
-                }
-            } else {
-                ui::warn(&format!("Skipping '{}' (already exists, use --force)", name));
-                continue;
-            }
+        if (link_path.exists() || link_path.is_symlink()) && !force {
+            ui::warn(&format!("Skipping '{}' (already exists, use --force)", name));
+            continue;
         }
 
-        symlink(&path, &link_path)?;
+        util::install_atomic(&link_path, true, &name, |staging| Ok(symlink(&path, staging)?))?;
         count += 1;
     }
 
@@ -391,7 +437,7 @@ fn install_all(persona_lib: &Path, global: bool, force: bool) -> Result<()> {
 }
 
 fn install_remote(spec_str: &str, global: bool, force: bool) -> Result<()> {
-    let spec = remote::parse_spec(spec_str)?;
+    let spec = remote::pin(remote::parse_spec(spec_str)?);
 
     // Repo-level: owner/repo with no path — discover and install all personas
     if spec.path.is_empty() {
@@ -401,12 +447,7 @@ fn install_remote(spec_str: &str, global: bool, force: bool) -> Result<()> {
     ui::info(&format!("Downloading {}...", spec));
 
     // Try single-file download first (personas are often a single .md)
-    let file_spec = remote::RemoteSpec {
-        owner: spec.owner.clone(),
-        repo: spec.repo.clone(),
-        path: format!("{}/PERSONA.md", spec.path),
-        git_ref: spec.git_ref.clone(),
-    };
+    let file_spec = spec.with_path(format!("{}/PERSONA.md", spec.path));
 
     let persona_name = spec
         .path
@@ -426,23 +467,27 @@ fn install_remote(spec_str: &str, global: bool, force: bool) -> Result<()> {
     fs::create_dir_all(&target_dir)?;
     let dest = target_dir.join(&persona_name);
 
-    util::ensure_target_clear(&dest, force, &persona_name)?;
-
-    // Try fetching as a directory (tarball)
-    match remote::fetch_dir(&spec) {
-        Ok((_tmp_dir, source_path)) => {
-            util::copy_dir_recursive(&source_path, &dest)?;
-            remote::write_metadata(&dest, &spec)?;
-        }
-        Err(_) => {
-            // Fallback: try single PERSONA.md file
-            let data = remote::fetch_file(&file_spec)
-                .context(format!("Failed to download persona '{}'", persona_name))?;
-
-            fs::create_dir_all(&dest)?;
-            fs::write(dest.join("PERSONA.md"), &data)?;
-            remote::write_metadata(&dest, &spec)?;
+    util::install_atomic(&dest, force, &persona_name, |staging| {
+        // Try fetching as a directory (tarball)
+        match remote::fetch_dir(&spec) {
+            Ok((_tmp_dir, source_path)) => {
+                remote::verify_item(&spec, &source_path)?;
+                util::copy_dir_recursive(&source_path, staging)?;
+            }
+            Err(_) => {
+                // Fallback: try single PERSONA.md file
+                let data = remote::fetch_file(&file_spec)
+                    .context(format!("Failed to download persona '{}'", persona_name))?;
+                remote::allow_unverified(&file_spec.to_string())?;
+
+                fs::create_dir_all(staging)?;
+                fs::write(staging.join("PERSONA.md"), &data)?;
+            }
         }
+        remote::write_metadata(staging, &spec)
+    })?;
+    if !global {
+        lock_persona(&dest, &persona_name, &spec)?;
     }
 
     let scope = if global { "global" } else { "local" };
@@ -454,14 +499,123 @@ fn install_remote(spec_str: &str, global: bool, force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Copy a fetched persona (directory or single .md file) into `dest`.
+pub fn copy_persona(source: &Path, dest: &Path) -> Result<()> {
+    if source.is_dir() {
+        util::copy_dir_recursive(source, dest)
+    } else {
+        fs::create_dir_all(dest)?;
+        fs::copy(source, dest.join("PERSONA.md"))?;
+        Ok(())
+    }
+}
+
+/// Record a project-scope remote persona install in agt.lock.
+/// Local sources are not recorded; see `skill::lock_skill`.
+pub fn lock_persona(dest: &Path, name: &str, spec: &remote::RemoteSpec) -> Result<()> {
+    if spec.transport.local_path().is_some() {
+        if !config::lockfile_path().exists() {
+            return Ok(());
+        }
+        return config::update_lockfile(|lock| lock.remove_persona(name));
+    }
+    let entry = config::LockEntry {
+        name: name.to_string(),
+        agent: None,
+        source: spec.to_string(),
+        commit: spec.commit.clone(),
+        hash: util::hash_dir(dest)?,
+    };
+    config::update_lockfile(|lock| lock.upsert_persona(entry))
+}
+
+/// Reinstall every persona recorded in agt.lock at its pinned commit.
+fn install_locked() -> Result<()> {
+    let path = config::lockfile_path();
+    let lock = config::parse_lockfile(&path)?
+        .context(format!("No lockfile found at {}", path.display()))?;
+
+    if lock.personas.is_empty() {
+        ui::info("No personas recorded in agt.lock.");
+        return Ok(());
+    }
+
+    let target_dir = config::local_persona_target();
+    fs::create_dir_all(&target_dir)?;
+
+    let mut installed = 0;
+    let mut unchanged = 0;
+    let mut failed = 0;
+    for entry in &lock.personas {
+        match install_locked_persona(entry, &target_dir) {
+            Ok(true) => installed += 1,
+            Ok(false) => unchanged += 1,
+            Err(e) => {
+                ui::warn(&format!("Failed to install '{}': {:#}", entry.name, e));
+                failed += 1;
+            }
+        }
+    }
+
+    ui::success(&format!(
+        "Locked install: {} installed, {} unchanged, {} failed",
+        installed, unchanged, failed
+    ));
+    if failed > 0 {
+        bail!("{} persona(s) could not be installed from agt.lock", failed);
+    }
+    if installed > 0 {
+        post_persona_install();
+    }
+    Ok(())
+}
+
+/// Install one locked persona. Returns false if the installed copy already matches.
+fn install_locked_persona(entry: &config::LockEntry, target_dir: &Path) -> Result<bool> {
+    util::validate_name(&entry.name)?;
+    let dest = target_dir.join(&entry.name);
+    if dest.is_dir() && !dest.is_symlink() && util::hash_dir(&dest)? == entry.hash {
+        return Ok(false);
+    }
+
+    let mut spec = remote::parse_spec(&entry.source)?;
+    spec.commit = entry.commit.clone();
+
+    // Stage into a scratch dir so the hash covers exactly what gets installed
+    let (_tmp_dir, source_path) = remote::fetch_dir(&spec)?;
+    remote::verify_item(&spec, &source_path)?;
+    let staging = tempfile::TempDir::new().context("Failed to create temp directory")?;
+    let staged = staging.path().join(&entry.name);
+    copy_persona(&source_path, &staged)?;
+
+    let hash = util::hash_dir(&staged)?;
+    if hash != entry.hash {
+        bail!(
+            "Content hash mismatch: agt.lock has {}, {} has {}",
+            entry.hash,
+            spec,
+            hash
+        );
+    }
+
+    util::install_atomic(&dest, true, &entry.name, |staging| {
+        util::copy_dir_recursive(&staged, staging)?;
+        remote::write_metadata(staging, &spec)
+    })?;
+
+    ui::success(&format!("Installed persona '{}' (local) from {}", entry.name, spec));
+    Ok(true)
+}
+
 fn install_remote_repo(spec: &remote::RemoteSpec, global: bool, force: bool) -> Result<()> {
-    ui::info(&format!("Downloading {}/{}@{}...", spec.owner, spec.repo, spec.git_ref));
+    ui::info(&format!("Downloading {}@{}...", spec.repo_label(), spec.git_ref));
     let (_tmp_dir, repo_root) = remote::fetch_dir(spec)?;
+    let verifier = remote::SourceVerifier::load(&repo_root, &spec.repo_label())?;
 
     // Look for personas/ directory in the repo
     let persona_dir = repo_root.join("personas");
     if !persona_dir.is_dir() {
-        bail!("No personas/ directory found in {}/{}", spec.owner, spec.repo);
+        bail!("No personas/ directory found in {}", spec.repo_label());
     }
 
     // Discover all personas in the repo
@@ -496,7 +650,7 @@ fn install_remote_repo(spec: &remote::RemoteSpec, global: bool, force: bool) ->
     }
 
     if available.is_empty() {
-        bail!("No personas found in {}/{}", spec.owner, spec.repo);
+        bail!("No personas found in {}", spec.repo_label());
     }
 
     ui::info(&format!("Found {} personas", available.len()));
@@ -540,42 +694,36 @@ fn install_remote_repo(spec: &remote::RemoteSpec, global: bool, force: bool) ->
             continue;
         }
 
+        if let Err(e) = verifier.check(path) {
+            ui::warn(&format!("Skipping persona '{}': {:#}", name, e));
+            skipped += 1;
+            continue;
+        }
+
         let dest = target_dir.join(name);
 
-        if dest.exists() || dest.is_symlink() {
-            if force {
-                if dest.is_symlink() || dest.is_file() {
-                    fs::remove_file(&dest)?;
-                } else {
-                    fs::remove_dir_all(&dest)?;
-                }
-            } else {
-                skipped += 1;
-                continue;
-            }
+        if (dest.exists() || dest.is_symlink()) && !force {
+            skipped += 1;
+            continue;
         }
 
-        if path.is_dir() {
-            util::copy_dir_recursive(path, &dest)?;
-        } else {
-            fs::create_dir_all(&dest)?;
-            fs::copy(path, dest.join("PERSONA.md"))?;
+        let persona_spec = spec.with_path(format!("personas/{}", raw_name));
+        util::install_atomic(&dest, true, name, |staging| {
+            copy_persona(path, staging)?;
+            remote::write_metadata(staging, &persona_spec)
+        })?;
+        if !global {
+            lock_persona(&dest, name, &persona_spec)?;
         }
-
-        let persona_spec = remote::RemoteSpec {
-            owner: spec.owner.clone(),
-            repo: spec.repo.clone(),
-            path: format!("personas/{}", raw_name),
-            git_ref: spec.git_ref.clone(),
-        };
-        remote::write_metadata(&dest, &persona_spec)?;
         ui::success(&format!("Installed persona '{}' ({})", name, scope));
         installed += 1;
     }
 
     ui::success(&format!(
-        "Done: {} personas installed, {} skipped from {}/{}",
-        installed, skipped, spec.owner, spec.repo
+        "Done: {} personas installed, {} skipped from {}",
+        installed,
+        skipped,
+        spec.repo_label()
     ));
     if installed > 0 {
         post_persona_install();
@@ -583,6 +731,32 @@ fn install_remote_repo(spec: &remote::RemoteSpec, global: bool, force: bool) ->
     Ok(())
 }
 
+fn rollback(name: &str, global: bool) -> Result<()> {
+    util::validate_name(name)?;
+    let (target_dir, scope) = if global {
+        (config::global_persona_target(), "global")
+    } else {
+        (config::local_persona_target(), "local")
+    };
+    let path = find_installed_persona(name, &target_dir).unwrap_or_else(|| target_dir.join(name));
+
+    util::restore_backup(&path, name)?;
+
+    if !global {
+        if !path.is_symlink() && path.join(".remote-source").exists() {
+            let spec = remote::parse_metadata(&path)?;
+            lock_persona(&path, name, &spec)?;
+        } else if config::lockfile_path().exists() {
+            config::update_lockfile(|lock| lock.remove_persona(name))?;
+        }
+    }
+
+    ui::success(&format!("Rolled back persona '{}' ({})", name, scope));
+    ui::hint("Run the same command again to undo the rollback");
+    post_persona_install();
+    Ok(())
+}
+
 fn uninstall(name: &str, global: bool) -> Result<()> {
     util::validate_name(name)?;
     // Try to find the persona: check dir, .md file, in both local and global
@@ -611,6 +785,10 @@ fn uninstall(name: &str, global: bool) -> Result<()> {
         fs::remove_dir_all(&path)?;
     }
 
+    if scope == "local" && config::lockfile_path().exists() {
+        config::update_lockfile(|lock| lock.remove_persona(name))?;
+    }
+
     ui::success(&format!("Uninstalled persona '{}' ({})", name, scope));
     Ok(())
 }
@@ -686,7 +864,7 @@ fn list(installed: bool, local: bool, global: bool, json: bool) -> Result<()> {
             return Ok(());
         }
         let mut table = ui::table::new_table();
-        table.set_header(&["Name", "Scope", "Role"]);
+        table.set_header(["Name", "Scope", "Role"]);
         for entry in &entries {
             let name = entry["name"].as_str().unwrap_or("");
             let scope = entry["scope"].as_str().unwrap_or("");
@@ -782,6 +960,30 @@ fn list(installed: bool, local: bool, global: bool, json: bool) -> Result<()> {
     Ok(())
 }
 
+fn outdated(only_global: bool, only_local: bool, json: bool) -> Result<()> {
+    let mut dirs = Vec::new();
+    if !only_global {
+        dirs.push(("local", config::local_persona_target()));
+    }
+    if !only_local {
+        dirs.push(("global", config::global_persona_target()));
+    }
+
+    let mut targets = Vec::new();
+    for (scope, dir) in dirs {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            if !name.starts_with('.') && path.join(".remote-source").exists() {
+                targets.push((name, scope.to_string(), path));
+            }
+        }
+    }
+    targets.sort();
+    super::outdated::report(&targets, "persona", json)
+}
+
 fn create(
     name: &str,
     ai: Option<String>,
@@ -840,7 +1042,7 @@ fn show(name: &str) -> Result<()> {
     let (fm, body) = frontmatter::parse(&content)?;
 
     let mut table = ui::table::new_table();
-    table.set_header(&["Field", "Value"]);
+    table.set_header(["Field", "Value"]);
     if let Some(n) = &fm.name {
         ui::table::add_row(&mut table, &["Name", n]);
     }
@@ -874,6 +1076,7 @@ fn which(name: &str) -> Result<()> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn review(
     name: &str,
     custom_prompt: Option<String>,
@@ -981,7 +1184,7 @@ fn find_persona(name: &str) -> Result<PathBuf> {
 /// Find the persona markdown content. Handles both:
 /// - Directory with PERSONA.md inside
 /// - Single .md file
-fn find_persona_md(path: &Path) -> Result<PathBuf> {
+pub fn find_persona_md(path: &Path) -> Result<PathBuf> {
     // If path is already a .md file
     if path.is_file() && path.extension().is_some_and(|e| e == "md") {
         return Ok(path.to_path_buf());
@@ -0,0 +1,257 @@
+use crate::cmd::skill;
+use crate::{config, remote, ui, util};
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub enum ProfileAction {
+    /// Create a profile from skills, groups and other profiles
+    Create {
+        /// Profile name
+        name: String,
+        /// Skills to include (group/name, optionally @<version constraint>)
+        skills: Vec<String>,
+        /// Short description
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Profiles to start from
+        #[arg(long, value_delimiter = ',')]
+        extends: Vec<String>,
+        /// Whole groups to include
+        #[arg(long, value_delimiter = ',')]
+        groups: Vec<String>,
+        /// Skills or groups to leave out
+        #[arg(long, value_delimiter = ',')]
+        exclude: Vec<String>,
+        /// Save to ~/.config/agt/profiles.yml instead of the project's .agents/profiles.yml
+        #[arg(long, conflicts_with = "output")]
+        user: bool,
+        /// Save to this profile file instead
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Replace an existing profile of the same name
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Show a profile's definition and the skills it resolves to
+    Show {
+        /// Profile name
+        name: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Save the currently installed skills as a profile
+    Export {
+        /// Profile name
+        name: String,
+        /// Export globally installed skills instead of the project's
+        #[arg(short, long)]
+        global: bool,
+        /// Agent whose skills to export
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
+        agent: config::SkillAgent,
+        /// Short description
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Save to ~/.config/agt/profiles.yml instead of the project's .agents/profiles.yml
+        #[arg(long, conflicts_with = "output")]
+        user: bool,
+        /// Save to this profile file instead
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Replace an existing profile of the same name
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+pub fn execute(action: ProfileAction) -> Result<()> {
+    match action {
+        ProfileAction::Create {
+            name,
+            skills,
+            description,
+            extends,
+            groups,
+            exclude,
+            user,
+            output,
+            force,
+        } => {
+            let def = config::ProfileDef {
+                description: description.unwrap_or_default(),
+                extends,
+                skills,
+                groups,
+                exclude,
+                ..Default::default()
+            };
+            create(&name, def, profile_file(user, output), force)
+        }
+        ProfileAction::Show { name, json } => show(&name, json),
+        ProfileAction::Export {
+            name,
+            global,
+            agent,
+            description,
+            user,
+            output,
+            force,
+        } => export(
+            &name,
+            global,
+            agent,
+            description,
+            profile_file(user, output),
+            force,
+        ),
+    }
+}
+
+/// Where `create` and `export` write: `--output`, the user file or the project file.
+fn profile_file(user: bool, output: Option<PathBuf>) -> PathBuf {
+    match output {
+        Some(path) => path,
+        None if user => config::user_profiles_path(),
+        None => config::project_profiles_path(),
+    }
+}
+
+fn create(name: &str, def: config::ProfileDef, path: PathBuf, force: bool) -> Result<()> {
+    util::validate_name(name)?;
+    if def.skills.is_empty() && def.groups.is_empty() && def.extends.is_empty() {
+        bail!("A profile needs at least one skill, --groups or --extends entry");
+    }
+    for entry in &def.skills {
+        if !entry.contains('/') {
+            bail!("Skill '{}' must be in group/name form", entry);
+        }
+    }
+
+    config::save_profile(&path, name, &def, force)?;
+    ui::success(&format!("Saved profile '{}' to {}", name, path.display()));
+
+    // Check references against the library, when there is one
+    if let Some(source_dir) = config::find_source_dir().or_else(config::find_cwd_source_dir) {
+        match config::resolve_profile(name, &source_dir) {
+            Ok(resolved) => ui::info(&format!("Resolves to {} skill(s)", resolved.skills.len())),
+            Err(e) => ui::warn(&format!("{:#}", e)),
+        }
+    }
+    Ok(())
+}
+
+fn show(name: &str, json: bool) -> Result<()> {
+    let source_dir = config::find_source_dir()
+        .or_else(config::find_cwd_source_dir)
+        .context(config::source_dir_hint())?;
+    let resolved = config::resolve_profile(name, &source_dir)?;
+    let def = config::find_profile(name, &source_dir)?.unwrap_or_default();
+    let origin = match &def.source {
+        Some(path) => path.display().to_string(),
+        None => "built-in".to_string(),
+    };
+    let entry = |group: &str, skill: &str| -> String {
+        let key = format!("{}/{}", group, skill);
+        match resolved.versions.get(&key) {
+            Some(req) => format!("{}@{}", key, req),
+            None => key,
+        }
+    };
+
+    if json {
+        let output = serde_json::json!({
+            "name": resolved.name,
+            "description": resolved.description,
+            "source": origin,
+            "extends": def.extends,
+            "exclude": def.exclude,
+            "skills": resolved
+                .skills
+                .iter()
+                .map(|(group, skill)| entry(group, skill))
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    ui::section(&format!("Profile: {}", resolved.name));
+    if !resolved.description.is_empty() {
+        eprintln!("  {}", resolved.description);
+    }
+    eprintln!("  {} {}", "Defined in:".dimmed(), origin);
+    if !def.extends.is_empty() {
+        eprintln!("  {} {}", "Extends:".dimmed(), def.extends.join(", "));
+    }
+    if !def.exclude.is_empty() {
+        eprintln!("  {} {}", "Excludes:".dimmed(), def.exclude.join(", "));
+    }
+    eprintln!();
+    for (group, skill) in &resolved.skills {
+        println!("{}", entry(group, skill));
+    }
+    eprintln!();
+    eprintln!("  {} skill(s)", resolved.skills.len());
+    Ok(())
+}
+
+fn export(
+    name: &str,
+    global: bool,
+    agent: config::SkillAgent,
+    description: Option<String>,
+    path: PathBuf,
+    force: bool,
+) -> Result<()> {
+    util::validate_name(name)?;
+    let target_dir = config::skill_target(global, agent);
+    let mut skills = Vec::new();
+    for (group, skill_name, skill_path) in skill::installed_skills(&target_dir) {
+        let group = if group.is_empty() {
+            skill::infer_skill_group(&skill_path)
+        } else {
+            group
+        };
+        if group.is_empty() {
+            ui::warn(&format!("Skipping '{}': cannot tell which group it belongs to", skill_name));
+            continue;
+        }
+        skills.push(format!("{}/{}{}", group, skill_name, version_constraint(&skill_path)));
+    }
+    if skills.is_empty() {
+        bail!("No installed skills in {}", target_dir.display());
+    }
+
+    let scope = if global { "global" } else { "local" };
+    let def = config::ProfileDef {
+        description: description
+            .unwrap_or_else(|| format!("Skills installed for {} ({})", agent, scope)),
+        skills,
+        ..Default::default()
+    };
+    config::save_profile(&path, name, &def, force)?;
+    ui::success(&format!(
+        "Exported {} skill(s) as profile '{}' to {}",
+        def.skills.len(),
+        name,
+        path.display()
+    ));
+    ui::hint(&format!("Install it elsewhere with: agt skill install --profile {}", name));
+    Ok(())
+}
+
+/// `@<constraint>` of a remote install made with one, so the export keeps it.
+fn version_constraint(skill_path: &Path) -> String {
+    if skill_path.is_symlink() {
+        return String::new();
+    }
+    remote::parse_metadata(skill_path)
+        .ok()
+        .and_then(|spec| spec.version_req)
+        .map(|req| format!("@{}", req))
+        .unwrap_or_default()
+}
@@ -44,22 +44,26 @@ pub fn execute(prompt: &str, skill: Option<&str>, llm_name: Option<&str>) -> Res
 }
 
 fn load_skill(name: &str) -> Result<String> {
-    util::validate_name(name)?;
     let installed_targets = [
         config::local_skill_target(),
         config::local_codex_skill_target(),
         config::global_skill_target(),
         config::global_codex_skill_target(),
     ];
+    // Qualified names (`group/name`, `source:group/name`) go straight to the sources
+    let qualified = name.contains(['/', ':']);
+    if !qualified {
+        util::validate_name(name)?;
+    }
 
     let skill_dir = if let Some(installed) = installed_targets
         .iter()
+        .filter(|_| !qualified)
         .find_map(|target| find_installed_skill(target, name))
     {
         installed
-    } else if let Some(source_dir) = config::find_source_dir().or_else(config::find_cwd_source_dir) {
-        find_skill_in_source(&source_dir, name)
-            .context(format!("Skill '{}' not found", name))?
+    } else if let Some((found, _)) = config::find_source_skill(name)? {
+        found.path
     } else {
         bail!("Skill '{}' not found", name);
     };
@@ -85,8 +89,9 @@ fn auto_match_skills(prompt: &str) -> Option<String> {
         collect_scored_skills(dir, &prompt_lower, &mut scored, &mut seen);
     }
 
-    // Scan library skills
-    if let Some(source_dir) = config::find_source_dir().or_else(config::find_cwd_source_dir) {
+    // Scan library skills in every source, highest priority first
+    for source in config::skill_sources() {
+        let source_dir = source.dir();
         for group in config::skill_groups(&source_dir) {
             collect_scored_skills(&source_dir.join(&group), &prompt_lower, &mut scored, &mut seen);
         }
@@ -96,7 +101,7 @@ fn auto_match_skills(prompt: &str) -> Option<String> {
         return None;
     }
 
-    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.sort_by_key(|s| std::cmp::Reverse(s.0));
 
     // Keep only skills scoring within half of the top match, capped at 5.
     // Drops weak namespace/tag-only matches when a clear winner exists.
@@ -202,16 +207,6 @@ fn score_skill(
     }
 }
 
-fn find_skill_in_source(source_dir: &Path, name: &str) -> Option<std::path::PathBuf> {
-    for group in config::skill_groups(source_dir) {
-        let path = source_dir.join(&group).join(name);
-        if path.is_dir() && path.join("SKILL.md").exists() {
-            return Some(path);
-        }
-    }
-    None
-}
-
 fn find_installed_skill(target_dir: &Path, name: &str) -> Option<std::path::PathBuf> {
     let direct = target_dir.join(name);
     if direct.join("SKILL.md").exists() {
@@ -0,0 +1,319 @@
+use crate::cmd::{persona, skill};
+use crate::{config, frontmatter, ui};
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A skill or persona that can match a search.
+struct Item {
+    kind: &'static str,
+    name: String,
+    /// `source:group/name` for library items, the path for installed-only ones
+    location: String,
+    path: PathBuf,
+    description: String,
+    tags: Vec<String>,
+    keywords: Vec<String>,
+    /// Persona role and domain
+    extra: Vec<String>,
+}
+
+pub fn execute(
+    query: &[String],
+    skills_only: bool,
+    personas_only: bool,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let terms: Vec<String> = query
+        .iter()
+        .flat_map(|q| q.split_whitespace())
+        .map(str::to_lowercase)
+        .collect();
+    if terms.is_empty() {
+        bail!("No search terms given. Usage: agt search <query>");
+    }
+
+    let installed = installed_locations();
+    let mut items = Vec::new();
+    if !personas_only {
+        collect_skills(&mut items);
+    }
+    if !skills_only {
+        collect_personas(&mut items);
+    }
+
+    let mut hits: Vec<(u32, &Item)> = items
+        .iter()
+        .map(|item| (score(item, &terms), item))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    hits.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    hits.truncate(limit);
+
+    let installed_in = |item: &Item| -> Vec<String> {
+        installed
+            .get(&(item.kind, item.name.clone()))
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    if json {
+        let output: Vec<serde_json::Value> = hits
+            .iter()
+            .map(|(score, item)| {
+                serde_json::json!({
+                    "kind": item.kind,
+                    "name": item.name,
+                    "location": item.location,
+                    "path": item.path.display().to_string(),
+                    "installed": installed_in(item),
+                    "description": item.description,
+                    "tags": item.tags,
+                    "score": score,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        ui::info(&format!("No skills or personas match '{}'", terms.join(" ")));
+        return Ok(());
+    }
+
+    ui::section(&format!("Search: {}", terms.join(" ")));
+    let mut table = ui::table::new_table();
+    table.set_header(["Kind", "Name", "Where", "Installed", "Description"]);
+    for (_, item) in &hits {
+        let installed = installed_in(item);
+        let installed = if installed.is_empty() {
+            "○".dimmed().to_string()
+        } else {
+            installed.join(", ").green().to_string()
+        };
+        let description = skill::truncate_description(&item.description)
+            .dimmed()
+            .to_string();
+        ui::table::add_row(&mut table, &[
+            item.kind,
+            &item.name,
+            &item.location,
+            &installed,
+            &description,
+        ]);
+    }
+    println!("{table}");
+    eprintln!();
+    eprintln!("  {} match(es)", hits.len());
+    Ok(())
+}
+
+/// Relevance of `item` to the lowercase `terms`; 0 means no match.
+/// Names weigh most, then trigger keywords, tags and descriptions.
+fn score(item: &Item, terms: &[String]) -> u32 {
+    let name = item.name.to_lowercase();
+    let description = item.description.to_lowercase();
+    let mut total = 0;
+    for term in terms {
+        let mut points = 0;
+        if name == *term {
+            points += 10;
+        } else if name.contains(term.as_str()) {
+            points += 6;
+        }
+        for keyword in &item.keywords {
+            let keyword = keyword.to_lowercase();
+            if keyword == *term {
+                points += 5;
+            } else if keyword.contains(term.as_str()) {
+                points += 3;
+            }
+        }
+        for tag in &item.tags {
+            if tag.to_lowercase() == *term {
+                points += 4;
+            }
+        }
+        if description.contains(term.as_str()) {
+            points += 2;
+        }
+        if item.extra.iter().any(|e| e.to_lowercase().contains(term.as_str())) {
+            points += 2;
+        }
+        total += points;
+    }
+    total
+}
+
+/// Library skills of every source, then installed skills no source has.
+fn collect_skills(items: &mut Vec<Item>) {
+    for source in config::skill_sources() {
+        let dir = source.dir();
+        for group in config::skill_groups(&dir) {
+            for name in config::skills_in_group(&dir, &group) {
+                let location = format!("{}:{}/{}", source.name, group, name);
+                items.extend(skill_item(&dir.join(&group).join(&name), &name, location));
+            }
+        }
+    }
+
+    for (_, target) in skill_targets() {
+        for (_, name, path) in skill::installed_skills(&target) {
+            if items.iter().any(|i| i.kind == "skill" && i.name == name) {
+                continue;
+            }
+            let location = path.display().to_string();
+            items.extend(skill_item(&path, &name, location));
+        }
+    }
+}
+
+fn skill_item(dir: &Path, name: &str, location: String) -> Option<Item> {
+    let content = fs::read_to_string(dir.join("SKILL.md")).ok()?;
+    let (fm, _) = frontmatter::parse(&content).ok()?;
+    Some(Item {
+        kind: "skill",
+        name: name.to_string(),
+        location,
+        path: dir.to_path_buf(),
+        description: fm.description.unwrap_or_default(),
+        tags: fm.tags.unwrap_or_default(),
+        keywords: fm.trigger_keywords.unwrap_or_default(),
+        extra: Vec::new(),
+    })
+}
+
+/// Personas of every source's library, then installed personas no source has.
+fn collect_personas(items: &mut Vec<Item>) {
+    let mut dirs: Vec<(Option<String>, PathBuf)> = config::skill_sources()
+        .into_iter()
+        .map(|s| (Some(s.name.clone()), config::persona_library(&s.dir())))
+        .collect();
+    dirs.push((None, config::local_persona_target()));
+    dirs.push((None, config::global_persona_target()));
+
+    for (source, dir) in dirs {
+        for (name, path) in persona_entries(&dir) {
+            if items.iter().any(|i| i.kind == "persona" && i.name == name) {
+                continue;
+            }
+            let location = match &source {
+                Some(source) => format!("{}:{}", source, name),
+                None => path.display().to_string(),
+            };
+            items.extend(persona_item(&path, &name, location));
+        }
+    }
+}
+
+/// Personas in a directory: `<name>/PERSONA.md` or `<name>.md`.
+fn persona_entries(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut entries = Vec::new();
+    let Ok(read) = fs::read_dir(dir) else {
+        return entries;
+    };
+    for entry in read.flatten() {
+        let raw = entry.file_name().to_string_lossy().to_string();
+        if raw.starts_with('.') || raw == "README.md" {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            entries.push((raw, path));
+        } else if let Some(name) = raw.strip_suffix(".md") {
+            entries.push((name.to_string(), path));
+        }
+    }
+    entries.sort();
+    entries
+}
+
+fn persona_item(path: &Path, name: &str, location: String) -> Option<Item> {
+    let md = persona::find_persona_md(path).ok()?;
+    let content = fs::read_to_string(md).ok()?;
+    let (fm, _) = frontmatter::parse(&content).ok()?;
+    let extra: Vec<String> = [fm.role.clone(), fm.domain].into_iter().flatten().collect();
+    Some(Item {
+        kind: "persona",
+        name: name.to_string(),
+        location,
+        path: path.to_path_buf(),
+        description: fm.description.or(fm.role).unwrap_or_default(),
+        tags: fm.tags.unwrap_or_default(),
+        keywords: fm.trigger_keywords.unwrap_or_default(),
+        extra,
+    })
+}
+
+/// Skill directories of every agent, as (`local (claude)`, path).
+fn skill_targets() -> Vec<(String, PathBuf)> {
+    let mut targets: Vec<(String, PathBuf)> = Vec::new();
+    for agent in config::SkillAgent::value_variants() {
+        for global in [false, true] {
+            let path = config::skill_target(global, *agent);
+            if targets.iter().any(|(_, p)| *p == path) {
+                continue;
+            }
+            let scope = if global { "global" } else { "local" };
+            targets.push((format!("{} ({})", scope, agent), path));
+        }
+    }
+    targets
+}
+
+/// Where each skill and persona is installed, keyed by (kind, name).
+fn installed_locations() -> BTreeMap<(&'static str, String), Vec<String>> {
+    let mut installed: BTreeMap<(&'static str, String), Vec<String>> = BTreeMap::new();
+    for (label, target) in skill_targets() {
+        for (_, name, _) in skill::installed_skills(&target) {
+            installed.entry(("skill", name)).or_default().push(label.clone());
+        }
+    }
+    for (scope, dir) in [
+        ("local", config::local_persona_target()),
+        ("global", config::global_persona_target()),
+    ] {
+        for (name, _) in persona_entries(&dir) {
+            installed.entry(("persona", name)).or_default().push(scope.to_string());
+        }
+    }
+    installed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, description: &str, keywords: &[&str]) -> Item {
+        Item {
+            kind: "skill",
+            name: name.to_string(),
+            location: String::new(),
+            path: PathBuf::new(),
+            description: description.to_string(),
+            tags: vec!["git".to_string()],
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            extra: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_score_ranks_names_over_descriptions() {
+        let terms = vec!["commit".to_string()];
+        let by_name = item("git-commit-pr", "Create pull requests", &[]);
+        let by_keyword = item("pr-review", "Review changes", &["commit message"]);
+        let by_description = item("changelog", "Summarize each commit", &[]);
+        let unrelated = item("deploy", "Ship it", &["release"]);
+
+        assert!(score(&by_name, &terms) > score(&by_keyword, &terms));
+        assert!(score(&by_keyword, &terms) > score(&by_description, &terms));
+        assert!(score(&by_description, &terms) > 0);
+        assert_eq!(score(&unrelated, &terms), 0);
+        assert_eq!(score(&unrelated, &["git".to_string()]), 4);
+    }
+}
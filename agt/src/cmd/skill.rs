@@ -1,8 +1,8 @@
-use crate::{config, frontmatter, remote, ui, util};
+use crate::{config, frontmatter, llm, remote, ui, util};
 use anyhow::{bail, Context, Result};
 use clap::Subcommand;
 use colored::Colorize;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::os::unix::fs::symlink;
 use std::path::{Path, PathBuf};
@@ -11,13 +11,13 @@ use std::path::{Path, PathBuf};
 pub enum SkillAction {
     /// Install a skill (local symlink or remote)
     Install {
-        /// Skill name (from source library)
+        /// Skill name, group/name, or <source>:group/name to pick a source
         name: Option<String>,
         /// Install globally in the selected agent's user skill directory
         #[arg(short, long)]
         global: bool,
         /// Agent whose skill directory should receive the installation
-        #[arg(long, value_enum, default_value_t)]
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
         agent: config::SkillAgent,
         /// Force overwrite existing
         #[arg(short, long)]
@@ -28,9 +28,13 @@ pub enum SkillAction {
         /// Install all available skills
         #[arg(short, long)]
         all: bool,
-        /// Remote spec: owner/repo/path[@ref]
+        /// Remote spec: [host/]owner/repo/path[@ref], a local directory, file:// URL or .tar.gz
+        /// (defaults to the `source` setting)
         #[arg(long, value_name = "SPEC")]
         from: Option<String>,
+        /// Reinstall exactly the skills recorded in the project's agt.lock
+        #[arg(long, conflicts_with_all = ["name", "global", "profile", "all", "from"])]
+        locked: bool,
     },
     /// Uninstall a skill
     Uninstall {
@@ -40,7 +44,21 @@ pub enum SkillAction {
         #[arg(short, long)]
         global: bool,
         /// Agent whose skill directory should be modified
-        #[arg(long, value_enum, default_value_t)]
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
+        agent: config::SkillAgent,
+        /// Uninstall even if other installed skills require it
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Restore the version replaced by the last install or update
+    Rollback {
+        /// Skill name
+        name: String,
+        /// Roll back the global install
+        #[arg(short, long)]
+        global: bool,
+        /// Agent whose skill directory should be modified
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
         agent: config::SkillAgent,
     },
     /// List available and installed skills
@@ -58,8 +76,15 @@ pub enum SkillAction {
         #[arg(long)]
         profiles: bool,
         /// Agent whose installed skills should be listed
-        #[arg(long, value_enum, default_value_t)]
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
         agent: config::SkillAgent,
+        /// List a remote library instead, from its index.json when it publishes one
+        #[arg(
+            long,
+            value_name = "SPEC",
+            conflicts_with_all = ["installed", "local", "global", "profiles"]
+        )]
+        from: Option<String>,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -67,15 +92,35 @@ pub enum SkillAction {
     /// Initialize skill directory in current project
     Init {
         /// Agent whose project skill directory should be created
-        #[arg(long, value_enum, default_value_t)]
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
         agent: config::SkillAgent,
     },
+    /// Scaffold a new skill in the source library (template or AI-drafted)
+    New {
+        /// Skill path as <group>/<name>
+        path: String,
+        /// Frontmatter description
+        #[arg(short, long, value_name = "TEXT")]
+        description: Option<String>,
+        /// Create a scripts/ directory
+        #[arg(long)]
+        scripts: bool,
+        /// Create a references/ directory
+        #[arg(long)]
+        references: bool,
+        /// Draft the body with AI (provide a description)
+        #[arg(long, value_name = "DESC")]
+        ai: Option<String>,
+        /// LLM to use with --ai: claude, codex, opencode, gemini, ollama
+        #[arg(long, requires = "ai")]
+        llm: Option<String>,
+    },
     /// Show the path of a skill
     Which {
         /// Skill name
         name: String,
         /// Agent whose installed skills should be searched
-        #[arg(long, value_enum, default_value_t)]
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
         agent: config::SkillAgent,
     },
     /// Update remote-installed skills
@@ -89,8 +134,72 @@ pub enum SkillAction {
         #[arg(short, long)]
         local: bool,
         /// Agent whose remote-installed skills should be updated
-        #[arg(long, value_enum, default_value_t)]
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
+        agent: config::SkillAgent,
+        /// Keep the local version of files that conflict with upstream
+        #[arg(long, conflicts_with = "theirs")]
+        ours: bool,
+        /// Take the upstream version of files that conflict with local edits
+        #[arg(long)]
+        theirs: bool,
+    },
+    /// Show local edits and upstream changes of copied remote skills as unified diffs
+    Diff {
+        /// Skill or group name (omit for all remote skills)
+        name: Option<String>,
+        /// Only global skills
+        #[arg(short, long)]
+        global: bool,
+        /// Only local skills
+        #[arg(short, long)]
+        local: bool,
+        /// Agent whose remote-installed skills should be compared
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
+        agent: config::SkillAgent,
+        /// Only compare against the version the skill was installed from
+        #[arg(long, conflicts_with = "upstream")]
+        installed: bool,
+        /// Only compare against the current upstream ref
+        #[arg(long)]
+        upstream: bool,
+    },
+    /// Show remote-installed skills whose upstream ref has moved (no downloads)
+    Outdated {
+        /// Check only global skills
+        #[arg(short, long)]
+        global: bool,
+        /// Check only local skills
+        #[arg(short, long)]
+        local: bool,
+        /// Agent whose remote-installed skills should be checked
+        #[arg(long, value_enum, default_value_t = config::default_agent())]
         agent: config::SkillAgent,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check skills for frontmatter, reference and structure problems
+    Lint {
+        /// Skill directory or library root (default: current directory or source library)
+        path: Option<PathBuf>,
+        /// Output diagnostics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Mirror installed skills from one agent's skill directory into another's
+    Sync {
+        /// Agent whose installed skills are copied
+        #[arg(long, value_enum)]
+        from_agent: config::SkillAgent,
+        /// Agent whose skill directory receives them
+        #[arg(long, value_enum)]
+        to_agent: config::SkillAgent,
+        /// Mirror global skills instead of project-local ones
+        #[arg(short, long)]
+        global: bool,
+        /// Overwrite differing skills already installed for the target agent
+        #[arg(short, long)]
+        force: bool,
     },
     /// Run a prompt with an optional skill (omit skill to call LLM directly)
     #[command(alias = "run")]
@@ -116,28 +225,89 @@ pub fn execute(action: SkillAction) -> Result<()> {
             profile,
             all,
             from,
-        } => install(name, global, agent, force, profile, all, from),
+            locked,
+        } => {
+            if locked {
+                install_locked()
+            } else {
+                let from = from.or_else(|| {
+                    let profile = if all { Some("all") } else { profile.as_deref() };
+                    fallback_source(name.as_deref(), profile)
+                });
+                install(name, global, agent, force, profile, all, from)
+            }
+        }
         SkillAction::Uninstall {
             name,
             global,
             agent,
-        } => uninstall(&name, global, agent),
+            force,
+        } => uninstall(&name, global, agent, force),
+        SkillAction::Rollback {
+            name,
+            global,
+            agent,
+        } => rollback(&name, global, agent),
         SkillAction::List {
             installed,
             local,
             global,
             profiles,
             agent,
+            from,
             json,
-        } => list(installed, local, global, profiles, agent, json),
+        } => match from {
+            Some(spec) => list_remote(&spec, agent, json),
+            None => list(installed, local, global, profiles, agent, json),
+        },
         SkillAction::Init { agent } => init(agent),
+        SkillAction::New {
+            path,
+            description,
+            scripts,
+            references,
+            ai,
+            llm,
+        } => new_skill(&path, description, scripts, references, ai, llm.as_deref()),
         SkillAction::Which { name, agent } => which(&name, agent),
         SkillAction::Update {
             name,
             global,
             local,
             agent,
-        } => update(name, global, local, agent),
+            ours,
+            theirs,
+        } => {
+            let strategy = if ours {
+                remote::MergeStrategy::Ours
+            } else if theirs {
+                remote::MergeStrategy::Theirs
+            } else {
+                remote::MergeStrategy::Merge
+            };
+            update(name, global, local, agent, strategy)
+        }
+        SkillAction::Diff {
+            name,
+            global,
+            local,
+            agent,
+            installed,
+            upstream,
+        } => diff(name, global, local, agent, !upstream, !installed),
+        SkillAction::Outdated {
+            global,
+            local,
+            agent,
+            json,
+        } => outdated(global, local, agent, json),
+        SkillAction::Lint { path, json } => super::lint::execute(path, json),
+        SkillAction::Sync {
+            from_agent,
+            to_agent,
+            global,
+            force,
+        } => sync_agents(from_agent, to_agent, global, force),
         SkillAction::Use { skill, llm, prompt } => {
             let prompt_str = prompt.join(" ");
             if prompt_str.trim().is_empty() {
@@ -148,6 +318,20 @@ pub fn execute(action: SkillAction) -> Result<()> {
     }
 }
 
+/// The `source` setting, when no local library or registered source can
+/// satisfy the request; an explicit `--from` always wins.
+fn fallback_source(name: Option<&str>, profile: Option<&str>) -> Option<String> {
+    let source = config::default_source()?;
+    let local = match (name, profile) {
+        (Some(name), _) => matches!(config::find_source_skill(name), Ok(Some(_))),
+        (None, Some(profile)) => config::find_source_dir()
+            .or_else(config::find_cwd_source_dir)
+            .is_some_and(|dir| config::resolve_profile(profile, &dir).is_ok()),
+        (None, None) => !config::skill_sources().is_empty(),
+    };
+    (!local).then_some(source)
+}
+
 fn install(
     name: Option<String>,
     global: bool,
@@ -197,22 +381,38 @@ fn install(
             return interactive_install(global, agent, force);
         }
     };
-    util::validate_name(&name)?;
 
-    let source_dir = config::find_source_dir()
-        .or_else(config::find_cwd_source_dir)
-        .context(config::source_dir_hint())?;
-
-    // Find skill in source
-    let skill_path = find_skill_in_source(&source_dir, &name)
-        .context(format!("Skill '{}' not found in source library", name))?;
-
-    // Extract group from skill_path (parent of skill dir)
-    let group = skill_path
-        .parent()
-        .and_then(|p| p.file_name())
-        .map(|g| g.to_string_lossy().to_string())
-        .unwrap_or_default();
+    // Search every source in priority order; `source:[group/]name` picks one
+    let Some((found, others)) = config::find_source_skill(&name)? else {
+        if config::skill_sources().is_empty() {
+            bail!("{}", config::source_dir_hint());
+        }
+        bail!("Skill '{}' not found in any source (see: agt source list)", name);
+    };
+    if !others.is_empty() {
+        ui::hint(&format!(
+            "'{}' is also in {}; install that one as <source>:{}/{}",
+            found.name,
+            others.join(", "),
+            found.group,
+            found.name
+        ));
+    }
+    if let Some(url) = &found.source.url {
+        let spec = remote::parse_spec(url)?;
+        let path = [spec.path.as_str(), &found.group, &found.name]
+            .iter()
+            .filter(|p| !p.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join("/");
+        let spec = remote::pin(spec.with_path(path));
+        return install_remote_spec(spec, global, agent, force, None, None);
+    }
+    let source_dir = found.source.dir();
+    let skill_path = found.path;
+    let group = found.group;
+    let name = found.name;
 
     let target_dir = config::skill_target(global, agent);
 
@@ -225,18 +425,38 @@ fn install(
         }
     }
 
-    let link_path = config::skill_destination(&target_dir, &group, &name, agent);
-    if let Some(parent) = link_path.parent() {
-        fs::create_dir_all(parent)?;
+    // Install what it requires first
+    let requested = (group.clone(), name.clone());
+    for dep in resolve_requires(&source_dir, std::slice::from_ref(&requested))? {
+        if dep == requested || is_skill_installed(&dep.1, agent) {
+            continue;
+        }
+        let (dep_group, dep_name) = dep;
+        let dep_path = source_dir.join(&dep_group).join(&dep_name);
+        let link_path = config::skill_destination(&target_dir, &dep_group, &dep_name, agent);
+        util::install_atomic(&link_path, false, &dep_name, |staging| {
+            symlink(&dep_path, staging).context(format!(
+                "Failed to create symlink for '{}/{}'",
+                dep_group, dep_name
+            ))
+        })?;
+        ui::success(&format!(
+            "Installed dependency '{}/{}' ({}, {})",
+            dep_group,
+            dep_name,
+            if global { "global" } else { "local" },
+            agent
+        ));
     }
 
-    util::ensure_target_clear(&link_path, force, &name)?;
-
-    symlink(&skill_path, &link_path).context(format!(
-        "Failed to create symlink: {} -> {}",
-        link_path.display(),
-        skill_path.display()
-    ))?;
+    let link_path = config::skill_destination(&target_dir, &group, &name, agent);
+    util::install_atomic(&link_path, force, &name, |staging| {
+        symlink(&skill_path, staging).context(format!(
+            "Failed to create symlink: {} -> {}",
+            link_path.display(),
+            skill_path.display()
+        ))
+    })?;
 
     let scope = if global { "global" } else { "local" };
     ui::success(&format!(
@@ -254,8 +474,18 @@ fn install_remote(
     profile: Option<&str>,
     requested_name: Option<&str>,
 ) -> Result<()> {
-    let spec = remote::parse_spec(spec_str)?;
+    let spec = remote::pin(remote::parse_spec(spec_str)?);
+    install_remote_spec(spec, global, agent, force, profile, requested_name)
+}
 
+fn install_remote_spec(
+    spec: remote::RemoteSpec,
+    global: bool,
+    agent: config::SkillAgent,
+    force: bool,
+    profile: Option<&str>,
+    requested_name: Option<&str>,
+) -> Result<()> {
     // Repo-level: owner/repo with no path — browse all skills
     if spec.path.is_empty() {
         return install_remote_repo(&spec, global, agent, force, profile, requested_name);
@@ -275,6 +505,12 @@ fn install_remote(
     if !source_path.join("SKILL.md").exists() {
         bail!("Remote path does not contain SKILL.md: {}", spec);
     }
+    remote::verify_item(&spec, &source_path)?;
+    if let Some(req) = &spec.version_req {
+        if skill_version(&source_path).is_some() {
+            check_skill_version(&source_path, req)?;
+        }
+    }
 
     let skill_name = source_path
         .file_name()
@@ -287,14 +523,10 @@ fn install_remote(
 
     let group = remote_skill_group(&spec.path);
     let dest = config::skill_destination(&target_dir, &group, &skill_name, agent);
-    if let Some(parent) = dest.parent() {
-        fs::create_dir_all(parent)?;
+    if !skill_requires(&source_path).is_empty() {
+        install_remote_requires(&spec, &group, &skill_name, global, agent)?;
     }
-
-    util::ensure_target_clear(&dest, force, &skill_name)?;
-
-    util::copy_dir_recursive(&source_path, &dest)?;
-    remote::write_metadata(&dest, &spec)?;
+    install_copy(&source_path, &dest, &spec, force, &skill_name)?;
 
     let scope = if global { "global" } else { "local" };
     let installed_name = if group.is_empty() {
@@ -302,6 +534,9 @@ fn install_remote(
     } else {
         format!("{group}/{skill_name}")
     };
+    if !global {
+        lock_skill(&dest, &installed_name, agent, &spec)?;
+    }
     ui::success(&format!(
         "Installed remote skill '{}' ({}, {}) from {}",
         installed_name, scope, agent, spec
@@ -325,11 +560,25 @@ fn install_remote_repo(
     profile: Option<&str>,
     requested_name: Option<&str>,
 ) -> Result<()> {
-    ui::info(&format!(
-        "Downloading {}/{}@{}...",
-        spec.owner, spec.repo, spec.git_ref
-    ));
+    let is_tty = console::Term::stderr().is_term();
+
+    // A published index.json lets the menu show before anything is downloaded
+    let mut preselected = None;
+    if is_tty && profile.is_none() && requested_name.is_none() {
+        if let Some(index) = remote::fetch_index(spec) {
+            match select_from_index(&index, global, agent)? {
+                Some(selection) => preselected = Some(selection),
+                None => {
+                    ui::info("Installation cancelled.");
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    ui::info(&format!("Downloading {}@{}...", spec.repo_label(), spec.git_ref));
     let (_tmp_dir, repo_root) = remote::fetch_dir(spec)?;
+    let verifier = remote::SourceVerifier::load(&repo_root, &spec.repo_label())?;
 
     // Discover skills in the repo (directories containing SKILL.md)
     let groups = config::skill_groups(&repo_root);
@@ -348,7 +597,7 @@ fn install_remote_repo(
             .unwrap_or(false);
 
     if all_skills.is_empty() {
-        bail!("No skills found in {}/{}", spec.owner, spec.repo);
+        bail!("No skills found in {}", spec.repo_label());
     }
 
     ui::info(&format!(
@@ -358,33 +607,54 @@ fn install_remote_repo(
         if has_personas { " (+ personas)" } else { "" }
     ));
 
-    // Interactive mode if TTY
-    let is_tty = console::Term::stderr().is_term();
-
     let target_dir = config::skill_target(global, agent);
     fs::create_dir_all(&target_dir)?;
 
     let scope = if global { "global" } else { "local" };
-    let skills_to_install = if let Some(requested_name) = requested_name {
+    // Profile entries like `group/name@^1.2` are fetched at their own tag
+    let mut versions = BTreeMap::new();
+    let skills_to_install = if let Some(mut selected) = preselected {
+        // The index is a separate file and may not match the downloaded tree
+        selected.skills.retain(|skill| {
+            let found = all_skills.contains(skill);
+            if !found {
+                ui::warn(&format!(
+                    "'{}/{}' is in the index but not in {}; skipping",
+                    skill.0,
+                    skill.1,
+                    spec.repo_label()
+                ));
+            }
+            found
+        });
+        versions = selected.versions;
+        versions.retain(|key, _| {
+            selected
+                .skills
+                .iter()
+                .any(|(group, name)| *key == format!("{}/{}", group, name))
+        });
+        selected.skills
+    } else if let Some(requested_name) = requested_name {
         util::validate_name(requested_name)?;
         let matches = skills_named(&all_skills, requested_name);
         match matches.len() {
             0 => bail!(
-                "Skill '{}' not found in {}/{}",
+                "Skill '{}' not found in {}",
                 requested_name,
-                spec.owner,
-                spec.repo
+                spec.repo_label()
             ),
             1 => matches,
             _ => bail!(
-                "Skill name '{}' is ambiguous in {}/{}",
+                "Skill name '{}' is ambiguous in {}",
                 requested_name,
-                spec.owner,
-                spec.repo
+                spec.repo_label()
             ),
         }
     } else if let Some(profile_name) = profile {
-        config::resolve_profile(profile_name, &repo_root)?.skills
+        let resolved = config::resolve_profile(profile_name, &repo_root)?;
+        versions = resolved.versions;
+        resolved.skills
     } else if is_tty {
         let local_installed = installed_skill_names(&config::skill_target(false, agent));
         let global_installed = installed_skill_names(&config::skill_target(true, agent));
@@ -400,6 +670,7 @@ fn install_remote_repo(
                     ui::info("Installation cancelled.");
                     return Ok(());
                 }
+                versions = resolved.versions;
                 resolved.skills
             }
             ui::interactive::InteractiveSelection::Skills(skills) => {
@@ -418,19 +689,67 @@ fn install_remote_repo(
         // Non-interactive: install all
         all_skills
     };
-
     let mut installed = 0;
     let mut skipped = 0;
+
+    // Fetch version-pinned skills first, so their `requires` are read from
+    // the tag being installed rather than the repository head
+    let mut versioned = BTreeMap::new();
+    for skill in &skills_to_install {
+        let key = format!("{}/{}", skill.0, skill.1);
+        let Some(req) = versions.get(&key) else {
+            continue;
+        };
+        match fetch_versioned_skill(spec, &key, req) {
+            Ok(fetched) => {
+                versioned.insert(skill.clone(), fetched);
+            }
+            Err(e) => {
+                ui::warn(&format!("Skipping '{}': {:#}", key, e));
+                skipped += 1;
+            }
+        }
+    }
+    let pinned_dirs: BTreeMap<(String, String), PathBuf> = versioned
+        .iter()
+        .map(|(skill, (_, path, _))| (skill.clone(), path.clone()))
+        .collect();
+    let skills_to_install: Vec<(String, String)> = skills_to_install
+        .into_iter()
+        .filter(|(group, name)| {
+            !versions.contains_key(&format!("{}/{}", group, name))
+                || pinned_dirs.contains_key(&(group.clone(), name.clone()))
+        })
+        .collect();
+    let skills_to_install = resolve_requires_in(&repo_root, &skills_to_install, &pinned_dirs)?;
+
     let local_dir = config::skill_target(false, agent);
     let global_dir = config::skill_target(true, agent);
 
-    for (group, skill_name) in &skills_to_install {
-        let source_path = repo_root.join(group).join(skill_name);
+    for skill in &skills_to_install {
+        let (group, skill_name) = skill;
+        let key = format!("{}/{}", group, skill_name);
+        let pinned = versioned.get(skill);
+        let (source_path, skill_spec) = match pinned {
+            Some((_, path, skill_spec)) => (path.clone(), skill_spec.clone()),
+            None => (repo_root.join(group).join(skill_name), spec.with_path(&key)),
+        };
         if !source_path.is_dir() || !source_path.join("SKILL.md").exists() {
             skipped += 1;
             continue;
         }
 
+        let verified = if pinned.is_some() {
+            remote::verify_item(&skill_spec, &source_path)
+        } else {
+            verifier.check(&source_path)
+        };
+        if let Err(e) = verified {
+            ui::warn(&format!("Skipping '{}': {:#}", key, e));
+            skipped += 1;
+            continue;
+        }
+
         // Check cross-scope duplicate
         if !force
             && warn_cross_scope_duplicate(skill_name, group, global, &local_dir, &global_dir)
@@ -440,41 +759,27 @@ fn install_remote_repo(
         }
 
         let dest = config::skill_destination(&target_dir, group, skill_name, agent);
-        if let Some(parent) = dest.parent() {
-            fs::create_dir_all(parent)?;
+        if (dest.exists() || dest.is_symlink()) && !force {
+            skipped += 1;
+            continue;
         }
 
-        if dest.exists() || dest.is_symlink() {
-            if force {
-                if dest.is_symlink() || dest.is_file() {
-                    fs::remove_file(&dest)?;
-                } else {
-                    fs::remove_dir_all(&dest)?;
-                }
-            } else {
-                skipped += 1;
-                continue;
-            }
+        install_copy(&source_path, &dest, &skill_spec, true, skill_name)?;
+        if !global {
+            lock_skill(&dest, &key, agent, &skill_spec)?;
         }
-
-        util::copy_dir_recursive(&source_path, &dest)?;
-        let skill_spec = remote::RemoteSpec {
-            owner: spec.owner.clone(),
-            repo: spec.repo.clone(),
-            path: format!("{}/{}", group, skill_name),
-            git_ref: spec.git_ref.clone(),
-        };
-        remote::write_metadata(&dest, &skill_spec)?;
         ui::success(&format!(
-            "Installed skill '{}/{}' ({}, {})",
-            group, skill_name, scope, agent
+            "Installed skill '{}' ({}, {})",
+            key, scope, agent
         ));
         installed += 1;
     }
 
     ui::success(&format!(
-        "Done: {} installed, {} skipped from {}/{}",
-        installed, skipped, spec.owner, spec.repo
+        "Done: {} installed, {} skipped from {}",
+        installed,
+        skipped,
+        spec.repo_label()
     ));
 
     // Run post-install setup from agt.toml manifest
@@ -485,6 +790,402 @@ fn install_remote_repo(
     Ok(())
 }
 
+/// Skills picked from a remote index menu, as a profile so a picked profile
+/// keeps its version constraints. `None` when the user cancels.
+fn select_from_index(
+    index: &remote::CatalogIndex,
+    global: bool,
+    agent: config::SkillAgent,
+) -> Result<Option<config::ResolvedProfile>> {
+    let local_installed = installed_skill_names(&config::skill_target(false, agent));
+    let global_installed = installed_skill_names(&config::skill_target(true, agent));
+    let selection =
+        ui::interactive::run_index_selector(index, &local_installed, &global_installed)?;
+    let selected = match selection {
+        ui::interactive::InteractiveSelection::Profile(name) => index.resolve_profile(&name)?,
+        ui::interactive::InteractiveSelection::Skills(skills) => config::ResolvedProfile {
+            name: String::new(),
+            description: String::new(),
+            skills,
+            versions: BTreeMap::new(),
+        },
+        _ => return Ok(None),
+    };
+    if !ui::interactive::confirm_install(&selected.skills, global)? {
+        return Ok(None);
+    }
+    Ok(Some(selected))
+}
+
+/// Fetch one skill of `spec`'s repository at the highest tag matching `req`,
+/// and check the version it declares, if any.
+fn fetch_versioned_skill(
+    spec: &remote::RemoteSpec,
+    path: &str,
+    req: &str,
+) -> Result<(tempfile::TempDir, PathBuf, remote::RemoteSpec)> {
+    let skill_spec = remote::pin(remote::RemoteSpec {
+        git_ref: req.to_string(),
+        commit: None,
+        version_req: Some(req.to_string()),
+        ..spec.with_path(path)
+    });
+    let (tmp_dir, source_path) = remote::fetch_dir(&skill_spec)?;
+    if skill_version(&source_path).is_some() {
+        check_skill_version(&source_path, req)?;
+    }
+    Ok((tmp_dir, source_path, skill_spec))
+}
+
+/// Atomically install a copy of a remote skill with its metadata and merge base.
+fn install_copy(
+    source_path: &Path,
+    dest: &Path,
+    spec: &remote::RemoteSpec,
+    force: bool,
+    skill_name: &str,
+) -> Result<()> {
+    util::install_atomic(dest, force, skill_name, |staging| {
+        util::copy_dir_recursive(source_path, staging)?;
+        remote::write_metadata(staging, spec)?;
+        remote::write_base(staging, source_path)
+    })
+}
+
+/// Install the skills a path-level remote skill requires from the same
+/// repository, skipping any already installed.
+fn install_remote_requires(
+    spec: &remote::RemoteSpec,
+    group: &str,
+    skill_name: &str,
+    global: bool,
+    agent: config::SkillAgent,
+) -> Result<()> {
+    // The library root is the parent of the skill's group directory
+    let root_path = Path::new(&spec.path)
+        .parent()
+        .and_then(Path::parent)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (_tmp_dir, root) = remote::fetch_dir(&spec.with_path(root_path.clone()))?;
+
+    let requested = (group.to_string(), skill_name.to_string());
+    let target_dir = config::skill_target(global, agent);
+    for dep in resolve_requires(&root, std::slice::from_ref(&requested))? {
+        if dep == requested || is_skill_installed(&dep.1, agent) {
+            continue;
+        }
+        let (dep_group, dep_name) = dep;
+        let rel = format!("{}/{}", dep_group, dep_name);
+        let dep_spec = if root_path.is_empty() {
+            spec.with_path(rel.clone())
+        } else {
+            spec.with_path(format!("{}/{}", root_path, rel))
+        };
+        let dep_path = root.join(&dep_group).join(&dep_name);
+        remote::verify_item(&dep_spec, &dep_path)?;
+        let dest = config::skill_destination(&target_dir, &dep_group, &dep_name, agent);
+        install_copy(&dep_path, &dest, &dep_spec, false, &dep_name)?;
+        if !global {
+            lock_skill(&dest, &rel, agent, &dep_spec)?;
+        }
+        ui::success(&format!("Installed dependency '{}' from {}", rel, dep_spec));
+    }
+    Ok(())
+}
+
+/// `skills` plus everything they transitively `require` in `source_dir`,
+/// dependencies ordered before their dependents.
+pub fn resolve_requires(
+    source_dir: &Path,
+    skills: &[(String, String)],
+) -> Result<Vec<(String, String)>> {
+    resolve_requires_in(source_dir, skills, &BTreeMap::new())
+}
+
+/// `resolve_requires`, reading the skills in `pinned` from those directories
+/// (fetched at another version) instead of from `source_dir`.
+fn resolve_requires_in(
+    source_dir: &Path,
+    skills: &[(String, String)],
+    pinned: &BTreeMap<(String, String), PathBuf>,
+) -> Result<Vec<(String, String)>> {
+    let mut order = Vec::new();
+    let mut stack = Vec::new();
+    for skill in skills {
+        visit_requires(source_dir, pinned, skill, &mut stack, &mut order)?;
+    }
+    Ok(order)
+}
+
+fn visit_requires(
+    source_dir: &Path,
+    pinned: &BTreeMap<(String, String), PathBuf>,
+    skill: &(String, String),
+    stack: &mut Vec<(String, String)>,
+    order: &mut Vec<(String, String)>,
+) -> Result<()> {
+    if order.contains(skill) {
+        return Ok(());
+    }
+    if let Some(start) = stack.iter().position(|s| s == skill) {
+        let cycle: Vec<String> = stack[start..]
+            .iter()
+            .chain([skill])
+            .map(|(group, name)| format!("{}/{}", group, name))
+            .collect();
+        bail!("Dependency cycle: {}", cycle.join(" → "));
+    }
+
+    stack.push(skill.clone());
+    let skill_dir = match pinned.get(skill) {
+        Some(dir) => dir.clone(),
+        None => source_dir.join(&skill.0).join(&skill.1),
+    };
+    for dep in skill_requires(&skill_dir) {
+        let found = find_required_skill(source_dir, &dep).context(format!(
+            "'{}/{}' requires '{}', which is not in {}",
+            skill.0,
+            skill.1,
+            dep,
+            source_dir.display()
+        ))?;
+        visit_requires(source_dir, pinned, &found, stack, order)?;
+    }
+    stack.pop();
+    order.push(skill.clone());
+    Ok(())
+}
+
+/// `version` from a skill's SKILL.md frontmatter.
+pub fn skill_version(skill_dir: &Path) -> Option<String> {
+    fs::read_to_string(skill_dir.join("SKILL.md"))
+        .ok()
+        .and_then(|content| frontmatter::parse(&content).ok())
+        .and_then(|(fm, _)| fm.version)
+}
+
+/// Fail unless the skill declares a `version` satisfying `req`.
+fn check_skill_version(skill_dir: &Path, req: &str) -> Result<()> {
+    let Some(version) = skill_version(skill_dir) else {
+        bail!("SKILL.md declares no version to match {}", req);
+    };
+    let parsed = remote::parse_version_req(req)?;
+    match remote::parse_version(&version) {
+        Some(v) if parsed.matches(&v) => Ok(()),
+        Some(_) => bail!("version {} does not satisfy {}", version, req),
+        None => bail!("invalid version '{}' in SKILL.md", version),
+    }
+}
+
+/// `requires` entries from a skill's SKILL.md frontmatter.
+pub fn skill_requires(skill_dir: &Path) -> Vec<String> {
+    fs::read_to_string(skill_dir.join("SKILL.md"))
+        .ok()
+        .and_then(|content| frontmatter::parse(&content).ok())
+        .and_then(|(fm, _)| fm.requires)
+        .unwrap_or_default()
+}
+
+/// Locate a `requires` entry (`group/name` or `name`) as (group, name).
+fn find_required_skill(source_dir: &Path, dep: &str) -> Option<(String, String)> {
+    let path = match dep.split_once('/') {
+        Some((group, name)) => {
+            Some(source_dir.join(group).join(name)).filter(|p| p.join("SKILL.md").exists())
+        }
+        None => find_skill_in_source(source_dir, dep),
+    }?;
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let group = path.parent()?.file_name()?.to_string_lossy().to_string();
+    Some((group, name))
+}
+
+/// Whether a skill is installed locally or globally for `agent`.
+fn is_skill_installed(name: &str, agent: config::SkillAgent) -> bool {
+    [false, true]
+        .iter()
+        .any(|&global| find_installed_skill(&config::skill_target(global, agent), name).is_some())
+}
+
+/// Installed skills in `target_dir`, other than `removing`, whose `requires`
+/// names one of `removing`.
+fn installed_dependents(target_dir: &Path, removing: &[PathBuf]) -> Vec<String> {
+    let names: Vec<(String, String)> = removing
+        .iter()
+        .map(|skill_path| {
+            let name = skill_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let group = match skill_path.parent() {
+                Some(parent) if parent != target_dir => parent
+                    .file_name()
+                    .map(|g| g.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                _ => infer_skill_group(skill_path),
+            };
+            let qualified = format!("{}/{}", group, name);
+            (name, qualified)
+        })
+        .collect();
+
+    installed_skills(target_dir)
+        .into_iter()
+        .filter(|(_, _, path)| !removing.contains(path))
+        .filter(|(_, _, path)| {
+            skill_requires(path).iter().any(|dep| {
+                names
+                    .iter()
+                    .any(|(name, qualified)| dep == name || dep == qualified)
+            })
+        })
+        .map(|(group, name, _)| if group.is_empty() { name } else { format!("{}/{}", group, name) })
+        .collect()
+}
+
+/// Refuse to remove `what` while installed skills require it, unless `force`.
+fn check_dependents(what: &str, dependents: &[String], force: bool) -> Result<()> {
+    if dependents.is_empty() {
+        return Ok(());
+    }
+    if !force {
+        bail!(
+            "'{}' is required by {} (use --force to uninstall anyway)",
+            what,
+            dependents.join(", ")
+        );
+    }
+    ui::warn(&format!("Uninstalling '{}' breaks {}", what, dependents.join(", ")));
+    Ok(())
+}
+
+/// Record a project-scope remote skill install in agt.lock.
+/// Local directories and bundles are machine-specific paths, so they are not
+/// recorded and replace any entry the name had. The hash is of the upstream
+/// snapshot, so local edits kept through updates do not end up in the lock.
+pub fn lock_skill(
+    dest: &Path,
+    name: &str,
+    agent: config::SkillAgent,
+    spec: &remote::RemoteSpec,
+) -> Result<()> {
+    if spec.transport.local_path().is_some() {
+        if !config::lockfile_path().exists() {
+            return Ok(());
+        }
+        return config::update_lockfile(|lock| lock.remove_skill(name, &agent.to_string()));
+    }
+    let entry = lock_entry(dest, name, agent, spec)?;
+    config::update_lockfile(|lock| lock.upsert_skill(entry))
+}
+
+fn lock_entry(
+    dest: &Path,
+    name: &str,
+    agent: config::SkillAgent,
+    spec: &remote::RemoteSpec,
+) -> Result<config::LockEntry> {
+    Ok(config::LockEntry {
+        name: name.to_string(),
+        agent: Some(agent.to_string()),
+        source: spec.to_string(),
+        commit: spec.commit.clone(),
+        hash: remote::upstream_hash(dest)?,
+    })
+}
+
+/// Reinstall every skill recorded in agt.lock at its pinned commit.
+fn install_locked() -> Result<()> {
+    let path = config::lockfile_path();
+    let lock = config::parse_lockfile(&path)?
+        .context(format!("No lockfile found at {}", path.display()))?;
+
+    if lock.skills.is_empty() {
+        ui::info("No skills recorded in agt.lock.");
+        return Ok(());
+    }
+
+    let mut installed = 0;
+    let mut unchanged = 0;
+    let mut failed = 0;
+    for entry in &lock.skills {
+        match install_locked_skill(entry) {
+            Ok(true) => installed += 1,
+            Ok(false) => unchanged += 1,
+            Err(e) => {
+                ui::warn(&format!("Failed to install '{}': {:#}", entry.name, e));
+                failed += 1;
+            }
+        }
+    }
+
+    ui::success(&format!(
+        "Locked install: {} installed, {} unchanged, {} failed",
+        installed, unchanged, failed
+    ));
+    if failed > 0 {
+        bail!("{} skill(s) could not be installed from agt.lock", failed);
+    }
+    Ok(())
+}
+
+/// Install one locked skill. Returns false if the installed copy already matches.
+fn install_locked_skill(entry: &config::LockEntry) -> Result<bool> {
+    let agent_name = entry.agent.as_deref().unwrap_or("claude");
+    let agent = <config::SkillAgent as clap::ValueEnum>::from_str(agent_name, true)
+        .map_err(|_| anyhow::anyhow!("Unknown agent '{}'", agent_name))?;
+    let (group, skill_name) = entry.name.rsplit_once('/').unwrap_or(("", &entry.name));
+    if !group.is_empty() {
+        util::validate_name(group)?;
+    }
+    util::validate_name(skill_name)?;
+
+    // An install at the locked version keeps its local edits
+    let dest = config::skill_destination(&config::skill_target(false, agent), group, skill_name, agent);
+    if dest.is_dir() && !dest.is_symlink() && remote::upstream_hash(&dest)? == entry.hash {
+        return Ok(false);
+    }
+
+    let mut spec = remote::parse_spec(&entry.source)?;
+    spec.commit = entry.commit.clone();
+    if spec.commit.is_none() && spec.transport.local_path().is_none() {
+        ui::warn(&format!(
+            "'{}' has no pinned commit in agt.lock; using {}",
+            entry.name, spec.git_ref
+        ));
+    }
+
+    let (_tmp_dir, source_path) = remote::fetch_dir(&spec)?;
+    remote::verify_item(&spec, &source_path)?;
+    check_locked_hash(entry, &spec, &source_path)?;
+
+    install_copy(&source_path, &dest, &spec, true, skill_name)?;
+
+    ui::success(&format!(
+        "Installed skill '{}' (local, {}) from {}",
+        entry.name, agent, spec
+    ));
+    Ok(true)
+}
+
+/// Refuse a fetched skill whose content differs from what agt.lock recorded.
+fn check_locked_hash(
+    entry: &config::LockEntry,
+    spec: &remote::RemoteSpec,
+    source_path: &Path,
+) -> Result<()> {
+    let hash = util::hash_dir(source_path)?;
+    if hash != entry.hash {
+        bail!(
+            "Content hash mismatch: agt.lock has {}, {} has {}",
+            entry.hash,
+            spec,
+            hash
+        );
+    }
+    Ok(())
+}
+
 fn skills_named(all_skills: &[(String, String)], requested_name: &str) -> Vec<(String, String)> {
     all_skills
         .iter()
@@ -585,7 +1286,7 @@ fn copy_file_with_strategy(source: &Path, target: &Path, strategy: &str) -> Resu
     Ok(1)
 }
 
-fn uninstall(name: &str, global: bool, agent: config::SkillAgent) -> Result<()> {
+fn uninstall(name: &str, global: bool, agent: config::SkillAgent, force: bool) -> Result<()> {
     let name = name.trim_end_matches('/');
     let target_dir = config::skill_target(global, agent);
     let scope = if global { "global" } else { "local" };
@@ -593,19 +1294,22 @@ fn uninstall(name: &str, global: bool, agent: config::SkillAgent) -> Result<()>
     // Check if name matches a real group directory (e.g. "acme/")
     let group_dir = target_dir.join(name);
     if group_dir.is_dir() && !group_dir.join("SKILL.md").exists() {
-        return uninstall_group(&group_dir, name, scope);
+        return uninstall_group(&target_dir, &group_dir, name, global, agent, force);
     }
 
     // Check if name matches a virtual group (e.g. "other" — flat skills with inferred group)
     let virtual_skills = find_virtual_group_skills(&target_dir, name);
     if !virtual_skills.is_empty() {
-        return uninstall_virtual_group(&virtual_skills, name, scope);
+        return uninstall_virtual_group(&target_dir, &virtual_skills, name, global, agent, force);
     }
 
     // Single skill
     let skill_path = find_installed_skill(&target_dir, name)
         .context(format!("Skill '{}' is not installed", name))?;
 
+    let dependents = installed_dependents(&target_dir, std::slice::from_ref(&skill_path));
+    check_dependents(name, &dependents, force)?;
+
     if skill_path.is_symlink() {
         fs::remove_file(&skill_path)?;
     } else {
@@ -619,6 +1323,10 @@ fn uninstall(name: &str, global: bool, agent: config::SkillAgent) -> Result<()>
         }
     }
 
+    if !global {
+        forget_locked_skill(&target_dir, &skill_path, agent)?;
+    }
+
     ui::success(&format!(
         "Uninstalled skill '{}' ({}, {})",
         name, scope, agent
@@ -626,8 +1334,103 @@ fn uninstall(name: &str, global: bool, agent: config::SkillAgent) -> Result<()>
     Ok(())
 }
 
+/// Drop an uninstalled skill from agt.lock, if the project has one.
+pub fn forget_locked_skill(target_dir: &Path, skill_path: &Path, agent: config::SkillAgent) -> Result<()> {
+    let lock_path = config::lockfile_path();
+    if !lock_path.exists() {
+        return Ok(());
+    }
+    let name = skill_path
+        .strip_prefix(target_dir)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    config::update_lockfile(|lock| lock.remove_skill(&name, &agent.to_string()))
+}
+
+/// Swap an installed skill with its backed-up previous version.
+fn rollback(name: &str, global: bool, agent: config::SkillAgent) -> Result<()> {
+    let scope = if global { "global" } else { "local" };
+    let target_dir = config::skill_target(global, agent);
+    for part in name.split('/') {
+        util::validate_name(part)?;
+    }
+    // A skill removed by `agt sync` only exists as a backup
+    let path = match find_installed_skill(&target_dir, name) {
+        Some(path) => path,
+        None => find_backed_up_skill(&target_dir, name, agent)?
+            .context(format!("Skill '{}' is not installed and has no backup", name))?,
+    };
+
+    util::restore_backup(&path, name)?;
+
+    // Keep agt.lock in step with the restored version
+    if !global {
+        forget_locked_skill(&target_dir, &path, agent)?;
+        if !path.is_symlink() && path.join(".remote-source").exists() {
+            let spec = remote::parse_metadata(&path)?;
+            let group = remote_skill_group(&spec.path);
+            let skill_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let locked_name = if group.is_empty() {
+                skill_name
+            } else {
+                format!("{}/{}", group, skill_name)
+            };
+            lock_skill(&path, &locked_name, agent, &spec)?;
+        }
+    }
+
+    ui::success(&format!(
+        "Rolled back skill '{}' ({}, {})",
+        name, scope, agent
+    ));
+    ui::hint("Run the same command again to undo the rollback");
+    Ok(())
+}
+
+/// Original path of a removed skill that has a backup: `group/name` maps to
+/// the agent's layout, a bare name is looked up flat and in backed-up groups.
+fn find_backed_up_skill(
+    target_dir: &Path,
+    name: &str,
+    agent: config::SkillAgent,
+) -> Result<Option<PathBuf>> {
+    let mut candidates = Vec::new();
+    match name.split_once('/') {
+        Some((group, skill_name)) => {
+            candidates.push(config::skill_destination(target_dir, group, skill_name, agent));
+        }
+        None => {
+            candidates.push(target_dir.join(name));
+            let backups = util::backup_path(target_dir)?;
+            for entry in fs::read_dir(&backups).into_iter().flatten().flatten() {
+                if entry.path().join(name).exists() {
+                    candidates.push(target_dir.join(entry.file_name()).join(name));
+                }
+            }
+        }
+    }
+    for path in candidates {
+        let backup = util::backup_path(&path)?;
+        if backup.exists() || backup.is_symlink() {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
 /// Uninstall all skills in a real group directory.
-fn uninstall_group(group_dir: &Path, group_name: &str, scope: &str) -> Result<()> {
+fn uninstall_group(
+    target_dir: &Path,
+    group_dir: &Path,
+    group_name: &str,
+    global: bool,
+    agent: config::SkillAgent,
+    force: bool,
+) -> Result<()> {
+    let scope = if global { "global" } else { "local" };
     let skills: Vec<String> = fs::read_dir(group_dir)?
         .flatten()
         .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
@@ -637,6 +1440,8 @@ fn uninstall_group(group_dir: &Path, group_name: &str, scope: &str) -> Result<()
     if skills.is_empty() {
         bail!("Group '{}' is empty", group_name);
     }
+    let paths: Vec<PathBuf> = skills.iter().map(|s| group_dir.join(s)).collect();
+    check_dependents(group_name, &installed_dependents(target_dir, &paths), force)?;
 
     if console::Term::stderr().is_term() {
         eprintln!("Will uninstall {} skills from group '{}':", skills.len(), group_name);
@@ -662,6 +1467,9 @@ fn uninstall_group(group_dir: &Path, group_name: &str, scope: &str) -> Result<()
         } else {
             fs::remove_dir_all(&path)?;
         }
+        if !global {
+            forget_locked_skill(target_dir, &path, agent)?;
+        }
         ui::success(&format!("Uninstalled skill '{}/{}' ({})", group_name, s, scope));
     }
     let _ = fs::remove_dir(group_dir);
@@ -705,7 +1513,16 @@ fn find_virtual_group_skills(target_dir: &Path, group_name: &str) -> Vec<PathBuf
 }
 
 /// Uninstall flat skills that belong to a virtual group.
-fn uninstall_virtual_group(skills: &[PathBuf], group_name: &str, scope: &str) -> Result<()> {
+fn uninstall_virtual_group(
+    target_dir: &Path,
+    skills: &[PathBuf],
+    group_name: &str,
+    global: bool,
+    agent: config::SkillAgent,
+    force: bool,
+) -> Result<()> {
+    let scope = if global { "global" } else { "local" };
+    check_dependents(group_name, &installed_dependents(target_dir, skills), force)?;
     if console::Term::stderr().is_term() {
         eprintln!("Will uninstall {} skills from '{}':", skills.len(), group_name);
         for s in skills {
@@ -730,6 +1547,9 @@ fn uninstall_virtual_group(skills: &[PathBuf], group_name: &str, scope: &str) ->
         } else {
             fs::remove_dir_all(path)?;
         }
+        if !global {
+            forget_locked_skill(target_dir, path, agent)?;
+        }
         ui::success(&format!("Uninstalled skill '{}' ({})", name, scope));
     }
     Ok(())
@@ -770,6 +1590,7 @@ fn install_profile(
         .or_else(config::find_cwd_source_dir)
         .context(config::source_dir_hint())?;
     let resolved = config::resolve_profile(profile_name, &source_dir)?;
+    let skills = resolve_requires(&source_dir, &resolved.skills)?;
 
     let target_dir = config::skill_target(global, agent);
     fs::create_dir_all(&target_dir)?;
@@ -778,22 +1599,30 @@ fn install_profile(
     ui::info(&format!(
         "Installing profile '{}': {} skills ({}, {})",
         resolved.name,
-        resolved.skills.len(),
+        skills.len(),
         scope,
         agent
     ));
+    if skills.len() > resolved.skills.len() {
+        ui::info(&format!(
+            "Including {} required skill(s)",
+            skills.len() - resolved.skills.len()
+        ));
+    }
 
     let mut installed = 0;
     let mut skipped = 0;
     let local_dir = config::skill_target(false, agent);
     let global_dir = config::skill_target(true, agent);
 
-    for (group, skill_name) in &resolved.skills {
+    for (group, skill_name) in &skills {
         let skill_path = source_dir.join(group).join(skill_name);
-        if !skill_path.is_dir() || !skill_path.join("SKILL.md").exists() {
-            ui::warn(&format!("Skill '{}/{}' not found, skipping", group, skill_name));
-            skipped += 1;
-            continue;
+        if let Some(req) = resolved.versions.get(&format!("{}/{}", group, skill_name)) {
+            if let Err(e) = check_skill_version(&skill_path, req) {
+                ui::warn(&format!("Skipping '{}/{}': {:#}", group, skill_name, e));
+                skipped += 1;
+                continue;
+            }
         }
 
         // Check cross-scope duplicate
@@ -805,27 +1634,17 @@ fn install_profile(
         }
 
         let link_path = config::skill_destination(&target_dir, group, skill_name, agent);
-        if let Some(parent) = link_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        if link_path.exists() || link_path.is_symlink() {
-            if force {
-                if link_path.is_symlink() || link_path.is_file() {
-                    fs::remove_file(&link_path)?;
-                } else {
-                    fs::remove_dir_all(&link_path)?;
-                }
-            } else {
-                skipped += 1;
-                continue;
-            }
+        if (link_path.exists() || link_path.is_symlink()) && !force {
+            skipped += 1;
+            continue;
         }
 
-        symlink(&skill_path, &link_path).context(format!(
-            "Failed to create symlink for '{}/{}'",
-            group, skill_name
-        ))?;
+        util::install_atomic(&link_path, true, skill_name, |staging| {
+            symlink(&skill_path, staging).context(format!(
+                "Failed to create symlink for '{}/{}'",
+                group, skill_name
+            ))
+        })?;
         installed += 1;
     }
 
@@ -992,6 +1811,7 @@ fn install_selected_skills(
     agent: config::SkillAgent,
     force: bool,
 ) -> Result<()> {
+    let skills = resolve_requires(source_dir, skills)?;
     let target_dir = config::skill_target(global, agent);
     fs::create_dir_all(&target_dir)?;
 
@@ -1001,7 +1821,7 @@ fn install_selected_skills(
     let mut installed = 0;
     let mut skipped = 0;
 
-    for (group, skill_name) in skills {
+    for (group, skill_name) in &skills {
         let skill_path = source_dir.join(group).join(skill_name);
         if !skill_path.is_dir() || !skill_path.join("SKILL.md").exists() {
             ui::warn(&format!("Skill '{}/{}' not found, skipping", group, skill_name));
@@ -1018,27 +1838,17 @@ fn install_selected_skills(
         }
 
         let link_path = config::skill_destination(&target_dir, group, skill_name, agent);
-        if let Some(parent) = link_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        if link_path.exists() || link_path.is_symlink() {
-            if force {
-                if link_path.is_symlink() || link_path.is_file() {
-                    fs::remove_file(&link_path)?;
-                } else {
-                    fs::remove_dir_all(&link_path)?;
-                }
-            } else {
-                skipped += 1;
-                continue;
-            }
+        if (link_path.exists() || link_path.is_symlink()) && !force {
+            skipped += 1;
+            continue;
         }
 
-        symlink(&skill_path, &link_path).context(format!(
-            "Failed to create symlink for '{}/{}'",
-            group, skill_name
-        ))?;
+        util::install_atomic(&link_path, true, skill_name, |staging| {
+            symlink(&skill_path, staging).context(format!(
+                "Failed to create symlink for '{}/{}'",
+                group, skill_name
+            ))
+        })?;
         ui::success(&format!(
             "Installed skill '{}/{}' ({}, {})",
             group, skill_name, scope, agent
@@ -1093,71 +1903,86 @@ fn list(
         return Ok(());
     }
 
-    // Default: grouped view showing all skills with install status
-    if let Some(source_dir) = config::find_source_dir().or_else(config::find_cwd_source_dir) {
-        let skill_groups = config::skill_groups(&source_dir);
+    // Default: grouped view showing all skills with install status, per source
+    let sources = config::skill_sources();
+    if !sources.is_empty() {
         let mut total = 0usize;
         let mut total_installed = 0usize;
 
         if json {
             // JSON mode: collect all entries
-            for group in &skill_groups {
-                let skills = config::skills_in_group(&source_dir, group);
-                for skill_name in &skills {
-                    let skill_path = source_dir.join(group).join(skill_name);
-                    let desc = read_skill_description(&skill_path);
-                    let status = if local_installed.contains(&skill_name.to_string()) {
-                        "local"
-                    } else if global_installed.contains(&skill_name.to_string()) {
-                        "global"
-                    } else {
-                        "available"
-                    };
-                    entries.push(serde_json::json!({
-                        "name": skill_name,
-                        "group": group,
-                        "status": status,
-                        "description": desc,
-                    }));
+            for source in &sources {
+                let source_dir = source.dir();
+                for group in &config::skill_groups(&source_dir) {
+                    let skills = config::skills_in_group(&source_dir, group);
+                    for skill_name in &skills {
+                        let skill_path = source_dir.join(group).join(skill_name);
+                        let desc = read_skill_description(&skill_path);
+                        let status = if local_installed.contains(&skill_name.to_string()) {
+                            "local"
+                        } else if global_installed.contains(&skill_name.to_string()) {
+                            "global"
+                        } else {
+                            "available"
+                        };
+                        entries.push(serde_json::json!({
+                            "name": skill_name,
+                            "group": group,
+                            "source": source.name,
+                            "status": status,
+                            "description": desc,
+                        }));
+                    }
                 }
             }
             println!("{}", serde_json::to_string_pretty(&entries)?);
             return Ok(());
         }
 
-        ui::section("Available Skills");
+        for source in &sources {
+            let source_dir = source.dir();
+            if sources.len() > 1 {
+                ui::section(&format!(
+                    "Available Skills: {} ({})",
+                    source.name,
+                    source.location()
+                ));
+            } else {
+                ui::section("Available Skills");
+            }
 
-        for group in &skill_groups {
-            let skills = config::skills_in_group(&source_dir, group);
-            let group_installed: usize = skills
-                .iter()
-                .filter(|s| local_installed.contains(*s) || global_installed.contains(*s))
-                .count();
+            for group in &config::skill_groups(&source_dir) {
+                let skills = config::skills_in_group(&source_dir, group);
+                let group_installed: usize = skills
+                    .iter()
+                    .filter(|s| local_installed.contains(*s) || global_installed.contains(*s))
+                    .count();
 
-            total += skills.len();
-            total_installed += group_installed;
+                total += skills.len();
+                total_installed += group_installed;
 
-            ui::subsection(&format!("{}/ ({}/{})", group, group_installed, skills.len()));
+                ui::subsection(&format!("{}/ ({}/{})", group, group_installed, skills.len()));
 
-            let mut table = ui::table::new_table();
-            for skill_name in &skills {
-                let status = if local_installed.contains(skill_name) {
-                    "L".green().bold().to_string()
-                } else if global_installed.contains(skill_name) {
-                    "G".blue().bold().to_string()
-                } else {
-                    "○".dimmed().to_string()
-                };
-                let desc = read_skill_description(&source_dir.join(group).join(skill_name));
-                let desc_styled = desc.dimmed().to_string();
-                ui::table::add_row(&mut table, &[
-                    status.as_str(),
-                    skill_name,
-                    desc_styled.as_str(),
-                ]);
-            }
-            if !skills.is_empty() {
-                println!("{table}");
+                let mut table = ui::table::new_table();
+                for skill_name in &skills {
+                    let status = if local_installed.contains(skill_name) {
+                        "L".green().bold().to_string()
+                    } else if global_installed.contains(skill_name) {
+                        "G".blue().bold().to_string()
+                    } else {
+                        "○".dimmed().to_string()
+                    };
+                    let desc = read_skill_description(&source_dir.join(group).join(skill_name));
+                    let desc_styled = desc.dimmed().to_string();
+                    ui::table::add_row(&mut table, &[
+                        status.as_str(),
+                        skill_name,
+                        desc_styled.as_str(),
+                    ]);
+                }
+                if !skills.is_empty() {
+                    println!("{table}");
+                }
             }
         }
 
@@ -1186,6 +2011,84 @@ fn list(
     Ok(())
 }
 
+/// List a remote library from its published index, downloading the
+/// repository only when it has none.
+fn list_remote(spec_str: &str, agent: config::SkillAgent, json: bool) -> Result<()> {
+    let spec = remote::pin(remote::parse_spec(spec_str)?);
+    let index = match remote::fetch_index(&spec) {
+        Some(index) => index,
+        None => {
+            ui::info(&format!(
+                "{} publishes no {}; downloading it",
+                spec.repo_label(),
+                remote::INDEX_FILE
+            ));
+            let (_tmp_dir, root) = remote::fetch_dir(&spec)?;
+            remote::build_index(&root)?
+        }
+    };
+    let local_installed = installed_skill_names(&config::skill_target(false, agent));
+    let global_installed = installed_skill_names(&config::skill_target(true, agent));
+    let status = |name: &String| {
+        if local_installed.contains(name) {
+            "local"
+        } else if global_installed.contains(name) {
+            "global"
+        } else {
+            "available"
+        }
+    };
+
+    if json {
+        let entries: Vec<serde_json::Value> = index
+            .skills
+            .iter()
+            .map(|skill| {
+                serde_json::json!({
+                    "name": skill.name,
+                    "group": skill.group,
+                    "status": status(&skill.name),
+                    "description": skill.description,
+                    "version": skill.version,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    ui::section(&format!("Available Skills: {}", spec.repo_label()));
+    let mut groups: BTreeMap<&str, Vec<&remote::IndexSkill>> = BTreeMap::new();
+    for skill in &index.skills {
+        groups.entry(skill.group.as_str()).or_default().push(skill);
+    }
+    let mut total_installed = 0;
+    for (group, skills) in &groups {
+        let group_installed = skills.iter().filter(|s| status(&s.name) != "available").count();
+        total_installed += group_installed;
+        ui::subsection(&format!("{}/ ({}/{})", group, group_installed, skills.len()));
+
+        let mut table = ui::table::new_table();
+        for skill in skills {
+            let status = match status(&skill.name) {
+                "local" => "L".green().bold().to_string(),
+                "global" => "G".blue().bold().to_string(),
+                _ => "○".dimmed().to_string(),
+            };
+            let desc = truncate_description(&skill.description).dimmed().to_string();
+            ui::table::add_row(&mut table, &[status.as_str(), &skill.name, desc.as_str()]);
+        }
+        println!("{table}");
+    }
+    ui::info(&format!(
+        "Total: {} skills, {} installed",
+        index.skills.len(),
+        total_installed
+    ));
+    ui::hint(&format!("Install with: agt skill install <name> --from {}", spec_str));
+    Ok(())
+}
+
 fn init(agent: config::SkillAgent) -> Result<()> {
     let dir = config::skill_target(false, agent);
     if dir.exists() {
@@ -1197,6 +2100,104 @@ fn init(agent: config::SkillAgent) -> Result<()> {
     Ok(())
 }
 
+fn new_skill(
+    path: &str,
+    description: Option<String>,
+    scripts: bool,
+    references: bool,
+    ai: Option<String>,
+    llm_name: Option<&str>,
+) -> Result<()> {
+    let Some((group, name)) = path.split_once('/') else {
+        bail!("Expected <group>/<name>, got '{}'", path);
+    };
+    util::validate_name(group)?;
+    util::validate_name(name)?;
+    if config::is_excluded(group) || group.starts_with('.') {
+        bail!("'{}' cannot be used as a skill group", group);
+    }
+
+    let source_dir = config::find_cwd_source_dir()
+        .or_else(config::find_source_dir)
+        .context(config::source_dir_hint())?;
+    if let Some(existing) = find_skill_in_source(&source_dir, name) {
+        bail!("Skill '{}' already exists at {}", name, existing.display());
+    }
+
+    let description = description
+        .or(ai.clone())
+        .unwrap_or_else(|| format!("TODO: describe what {} does and when to use it", name));
+    let body = match &ai {
+        Some(desc) => {
+            let cli = llm_name.map(llm::parse_cli).transpose()?;
+            generate_skill_body(name, desc, cli)?
+        }
+        None => default_skill_body(name),
+    };
+
+    let skill_dir = source_dir.join(group).join(name);
+    fs::create_dir_all(&skill_dir)?;
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        skill_markdown(name, &description, &body)?,
+    )?;
+    if scripts {
+        fs::create_dir_all(skill_dir.join("scripts"))?;
+    }
+    if references {
+        fs::create_dir_all(skill_dir.join("references"))?;
+    }
+
+    ui::success(&format!("Created skill '{}/{}' at {}", group, name, skill_dir.display()));
+    ui::hint(&format!("Check it with: agt skill lint {}", skill_dir.display()));
+    Ok(())
+}
+
+/// SKILL.md with a frontmatter block the linter accepts.
+fn skill_markdown(name: &str, description: &str, body: &str) -> Result<String> {
+    let description = serde_yaml::to_string(description)?;
+    Ok(format!(
+        "---\nname: {}\ndescription: {}---\n\n{}\n",
+        name,
+        description,
+        body.trim()
+    ))
+}
+
+fn default_skill_body(name: &str) -> String {
+    format!(
+        "# {}\n\n## When to use\n\nDescribe the situations that should trigger this skill.\n\n\
+         ## Instructions\n\n1. Step one\n2. Step two\n",
+        name
+    )
+}
+
+fn generate_skill_body(
+    name: &str,
+    desc: &str,
+    cli_override: Option<llm::LlmCli>,
+) -> Result<String> {
+    let cli = cli_override
+        .or_else(llm::detect)
+        .context("No LLM CLI found for skill generation")?;
+
+    ui::info(&format!("Drafting skill with {}...", cli));
+
+    let prompt = format!(
+        "Write the markdown body of a SKILL.md file for an AI coding agent skill.\n\n\
+         Name: {}\nDescription: {}\n\n\
+         Start with a `# {}` heading, then explain when to use the skill and give \
+         step-by-step instructions.\n\n\
+         Output ONLY the markdown body, without YAML frontmatter or explanation.",
+        name, desc, name
+    );
+
+    let output = llm::invoke(cli, &prompt)?;
+    // Drop any frontmatter the model added anyway; ours is written separately
+    let (_, body) = frontmatter::parse(&output)?;
+    Ok(body)
+}
+
 fn which(name: &str, agent: config::SkillAgent) -> Result<()> {
     // Check local (grouped then flat)
     let local_dir = config::skill_target(false, agent);
@@ -1214,33 +2215,42 @@ fn which(name: &str, agent: config::SkillAgent) -> Result<()> {
         return Ok(());
     }
 
-    // Check source library
-    if let Some(source_dir) = config::find_source_dir().or_else(config::find_cwd_source_dir) {
-        if let Some(path) = find_skill_in_source(&source_dir, name) {
-            println!("{}", path.display());
-            return Ok(());
-        }
+    // Check the sources in priority order
+    if let Some((found, _)) = config::find_source_skill(name)? {
+        println!("{}", found.path.display());
+        return Ok(());
     }
 
     bail!("Skill '{}' not found", name);
 }
 
-fn update(
-    name: Option<String>,
+/// Skill target directories selected by --global/--local, local first.
+fn scoped_targets(
     only_global: bool,
     only_local: bool,
     agent: config::SkillAgent,
-) -> Result<()> {
-    let mut targets: Vec<(&str, PathBuf)> = Vec::new();
-
+) -> Vec<(&'static str, PathBuf)> {
+    let mut targets = Vec::new();
     if !only_global {
         targets.push(("local", config::skill_target(false, agent)));
     }
     if !only_local {
         targets.push(("global", config::skill_target(true, agent)));
     }
+    targets
+}
+
+fn update(
+    name: Option<String>,
+    only_global: bool,
+    only_local: bool,
+    agent: config::SkillAgent,
+    strategy: remote::MergeStrategy,
+) -> Result<()> {
+    let targets = scoped_targets(only_global, only_local, agent);
 
     let mut total_updated = 0usize;
+    let mut total_unchanged = 0usize;
     let mut total_failed = 0usize;
     let mut found_any = false;
 
@@ -1260,8 +2270,9 @@ fn update(
         found_any = true;
 
         for (skill_path, display_name) in &remote_skills {
-            match update_single_skill(skill_path, display_name, scope) {
-                Ok(()) => total_updated += 1,
+            match update_single_skill(skill_path, display_name, scope, agent, strategy) {
+                Ok(true) => total_updated += 1,
+                Ok(false) => total_unchanged += 1,
                 Err(e) => {
                     ui::warn(&format!("Failed to update '{}': {:#}", display_name, e));
                     total_failed += 1;
@@ -1278,15 +2289,177 @@ fn update(
         }
     } else {
         ui::success(&format!(
-            "Update complete: {} updated, {} failed",
-            total_updated, total_failed
+            "Update complete: {} updated, {} up to date, {} failed",
+            total_updated, total_unchanged, total_failed
         ));
     }
 
     Ok(())
 }
 
+fn outdated(
+    only_global: bool,
+    only_local: bool,
+    agent: config::SkillAgent,
+    json: bool,
+) -> Result<()> {
+    let mut targets = Vec::new();
+    for (scope, target_dir) in scoped_targets(only_global, only_local, agent) {
+        for (path, name) in find_all_remote_skills(&target_dir) {
+            targets.push((name, scope.to_string(), path));
+        }
+    }
+    super::outdated::report(&targets, "skill", json)
+}
+
 /// Scan a target directory for all skills that have .remote-source metadata.
+/// Copy every skill installed for `from` into `to`'s layout. Library
+/// symlinks stay symlinks; copies keep their `.remote-source` metadata.
+fn sync_agents(
+    from: config::SkillAgent,
+    to: config::SkillAgent,
+    global: bool,
+    force: bool,
+) -> Result<()> {
+    if from == to {
+        bail!("--from-agent and --to-agent must differ");
+    }
+    let scope = if global { "global" } else { "local" };
+    let source_dir = config::skill_target(global, from);
+    let target_dir = config::skill_target(global, to);
+
+    let skills = installed_skills(&source_dir);
+    if skills.is_empty() {
+        ui::info(&format!("No {} skills installed for {}.", scope, from));
+        return Ok(());
+    }
+
+    let mut mirrored = 0;
+    let mut unchanged = 0;
+    let mut skipped = 0;
+    let mut taken = HashSet::new();
+    for (group, skill_name, path) in &skills {
+        let group = if group.is_empty() { infer_skill_group(path) } else { group.clone() };
+        let label = if group.is_empty() {
+            skill_name.clone()
+        } else {
+            format!("{}/{}", group, skill_name)
+        };
+        let dest = config::skill_destination(&target_dir, &group, skill_name, to);
+
+        // Flat layouts cannot hold two skills of the same name
+        if !taken.insert(dest.clone()) {
+            ui::warn(&format!(
+                "Skipping '{}': {} already received a skill named '{}'",
+                label, to, skill_name
+            ));
+            skipped += 1;
+            continue;
+        }
+
+        let link_target = if path.is_symlink() {
+            match fs::canonicalize(path) {
+                Ok(target) => Some(target),
+                Err(_) => {
+                    ui::warn(&format!("Skipping '{}': broken symlink", label));
+                    skipped += 1;
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+
+        if dest.exists() || dest.is_symlink() {
+            let same = match &link_target {
+                Some(target) => {
+                    dest.is_symlink() && fs::canonicalize(&dest).ok().as_ref() == Some(target)
+                }
+                None => {
+                    !dest.is_symlink()
+                        && util::hash_dir(&dest).ok() == util::hash_dir(path).ok()
+                }
+            };
+            if same {
+                unchanged += 1;
+                continue;
+            }
+            if !force {
+                ui::warn(&format!(
+                    "Skipping '{}': differs from the {} copy (use --force to overwrite)",
+                    label, to
+                ));
+                skipped += 1;
+                continue;
+            }
+        }
+
+        util::install_atomic(&dest, true, skill_name, |staging| match &link_target {
+            Some(target) => symlink(target, staging)
+                .context(format!("Failed to create symlink for '{}'", label)),
+            None => util::copy_dir_recursive(path, staging),
+        })?;
+        if link_target.is_none() && !global && dest.join(".remote-source").exists() {
+            let spec = remote::parse_metadata(&dest)?;
+            lock_skill(&dest, &label, to, &spec)?;
+        }
+        mirrored += 1;
+    }
+
+    ui::success(&format!(
+        "Mirrored {} → {} ({}): {} installed, {} unchanged, {} skipped",
+        from, to, scope, mirrored, unchanged, skipped
+    ));
+    Ok(())
+}
+
+/// Installed skills in a target directory as (group, name, path), with
+/// one level of group directories; flat skills have an empty group.
+pub fn installed_skills(target_dir: &Path) -> Vec<(String, String, PathBuf)> {
+    let mut skills = Vec::new();
+    let Ok(entries) = fs::read_dir(target_dir) else {
+        return skills;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() && !path.is_symlink() && !path.join("SKILL.md").exists() {
+            if let Ok(children) = fs::read_dir(&path) {
+                for child in children.flatten() {
+                    let child_name = child.file_name().to_string_lossy().to_string();
+                    if !child_name.starts_with('.') && child.path().join("SKILL.md").exists() {
+                        skills.push((name.clone(), child_name, child.path()));
+                    }
+                }
+            }
+        } else if path.join("SKILL.md").exists() {
+            skills.push((String::new(), name, path));
+        }
+    }
+    skills.sort();
+    skills
+}
+
+/// Group of a flat-installed skill: the library group a symlink points
+/// into, or the group recorded in `.remote-source`.
+pub fn infer_skill_group(path: &Path) -> String {
+    if path.is_symlink() {
+        return fs::canonicalize(path)
+            .ok()
+            .and_then(|target| {
+                let group = target.parent()?.file_name()?;
+                Some(group.to_string_lossy().to_string())
+            })
+            .unwrap_or_default();
+    }
+    remote::parse_metadata(path)
+        .map(|spec| remote_skill_group(&spec.path))
+        .unwrap_or_default()
+}
+
 fn find_all_remote_skills(target_dir: &Path) -> Vec<(PathBuf, String)> {
     let mut results = Vec::new();
 
@@ -1381,37 +2554,256 @@ fn find_update_targets(target_dir: &Path, name: &str) -> Result<Vec<(PathBuf, St
     Ok(vec![])
 }
 
+fn diff(
+    name: Option<String>,
+    only_global: bool,
+    only_local: bool,
+    agent: config::SkillAgent,
+    against_installed: bool,
+    against_upstream: bool,
+) -> Result<()> {
+    let mut found_any = false;
+    for (scope, target_dir) in scoped_targets(only_global, only_local, agent) {
+        if !target_dir.is_dir() {
+            continue;
+        }
+        let remote_skills = match &name {
+            Some(n) => find_update_targets(&target_dir, n)?,
+            None => find_all_remote_skills(&target_dir),
+        };
+        for (skill_path, display_name) in &remote_skills {
+            found_any = true;
+            let label = format!("{} ({})", display_name, scope);
+            let result =
+                diff_single_skill(skill_path, &label, against_installed, against_upstream);
+            if let Err(e) = result {
+                ui::warn(&format!("Failed to diff '{}': {:#}", label, e));
+            }
+        }
+    }
+
+    if !found_any {
+        match name {
+            Some(n) => bail!("No remote skill '{}' found", n),
+            None => ui::info("No remote-installed skills found."),
+        }
+    }
+    Ok(())
+}
+
+/// Print diffs of one copied skill: installed version → local copy, and
+/// local copy → current upstream ref.
+fn diff_single_skill(
+    skill_path: &Path,
+    label: &str,
+    against_installed: bool,
+    against_upstream: bool,
+) -> Result<()> {
+    let installed = remote::parse_metadata(skill_path)?;
+    let local = remote::read_tree(skill_path)?;
+    let color = console::Term::stdout().is_term();
+
+    if against_installed {
+        let base = match remote::read_base(skill_path)? {
+            Some(base) => base,
+            None => {
+                // Installed before snapshots were recorded: refetch the pinned commit
+                if installed.commit.is_none() {
+                    ui::warn(&format!(
+                        "'{}' has no recorded snapshot; comparing with the source as it is now",
+                        label
+                    ));
+                }
+                let (_tmp_dir, source_path) = remote::fetch_dir(&installed)?;
+                remote::read_tree(&source_path)?
+            }
+        };
+        print_diff(
+            &format!("{}: local edits since install{}", label, version_suffix(&installed)),
+            &remote::diff_trees(&base, &local, color),
+        );
+    }
+
+    if against_upstream {
+        let latest = remote::pin(remote::RemoteSpec {
+            commit: None,
+            ..installed.clone()
+        });
+        let (_tmp_dir, source_path) = remote::fetch_dir(&latest)?;
+        remote::verify_item(&latest, &source_path)?;
+        let upstream = remote::read_tree(&source_path)?;
+        print_diff(
+            &format!("{}: local → upstream{}", label, version_suffix(&latest)),
+            &remote::diff_trees(&local, &upstream, color),
+        );
+    }
+    Ok(())
+}
+
+/// ` (<commit or ref>)`, or nothing for unversioned local sources.
+fn version_suffix(spec: &remote::RemoteSpec) -> String {
+    match spec.commit.as_deref().map(remote::short_commit) {
+        Some(commit) => format!(" ({})", commit),
+        None if !spec.git_ref.is_empty() => format!(" ({})", spec.git_ref),
+        None => String::new(),
+    }
+}
+
+fn print_diff(title: &str, diff: &str) {
+    ui::section(title);
+    if diff.is_empty() {
+        ui::info("No differences");
+    } else {
+        print!("{}", diff);
+    }
+}
+
+/// Merge `upstream` into an installed copy and record it as the new base.
+fn apply_update(
+    skill_dir: &Path,
+    upstream: &Path,
+    latest: &remote::RemoteSpec,
+    strategy: remote::MergeStrategy,
+) -> Result<remote::MergeReport> {
+    let report = remote::merge_update(skill_dir, upstream, strategy)?;
+    remote::write_metadata(skill_dir, latest)?;
+    remote::write_base(skill_dir, upstream)?;
+    Ok(report)
+}
+
 /// Update a single remote skill by re-fetching from its original source.
-fn update_single_skill(skill_path: &Path, display_name: &str, scope: &str) -> Result<()> {
-    let spec = remote::parse_metadata(skill_path)?;
+/// Returns false when the upstream commit has not moved since install.
+fn update_single_skill(
+    skill_path: &Path,
+    display_name: &str,
+    scope: &str,
+    agent: config::SkillAgent,
+    strategy: remote::MergeStrategy,
+) -> Result<bool> {
+    let installed = remote::parse_metadata(skill_path)?;
+    let latest = remote::pin(remote::RemoteSpec {
+        commit: None,
+        ..installed.clone()
+    });
 
-    ui::info(&format!(
-        "Updating '{}' ({}) from {}...",
-        display_name, scope, spec
-    ));
+    if installed.commit.is_some() && installed.commit == latest.commit {
+        ui::info(&format!(
+            "'{}' ({}) is up to date ({})",
+            display_name,
+            scope,
+            remote::short_commit(latest.download_ref())
+        ));
+        return Ok(false);
+    }
 
-    let (_tmp_dir, source_path) = remote::fetch_dir(&spec)?;
+    let (_tmp_dir, source_path) = remote::fetch_dir(&latest)?;
 
     if !source_path.join("SKILL.md").exists() {
         bail!("Remote source no longer contains SKILL.md");
     }
+    remote::verify_item(&latest, &source_path)?;
 
-    // Replace: remove old, copy new
-    if skill_path.is_dir() {
-        fs::remove_dir_all(skill_path)?;
+    // Without commits to compare (local sources, unpinned refs), compare content
+    if latest.commit.is_none() && util::hash_dir(&source_path)? == util::hash_dir(skill_path)? {
+        ui::info(&format!("'{}' ({}) is up to date", display_name, scope));
+        return Ok(false);
     }
-    util::copy_dir_recursive(&source_path, skill_path)?;
-    remote::write_metadata(skill_path, &spec)?;
 
-    ui::success(&format!("Updated '{}' ({})", display_name, scope));
-    Ok(())
+    ui::info(&format!(
+        "Updating '{}' ({}) from {}...",
+        display_name, scope, latest
+    ));
+
+    if !skill_path.join(remote::BASE_FILE).exists() {
+        ui::warn(&format!(
+            "'{}' has no recorded upstream snapshot; local edits will be replaced",
+            display_name
+        ));
+    }
+    // Merge into a staged copy so a failure leaves the installed version intact
+    let mut report = remote::MergeReport::default();
+    util::install_atomic(skill_path, true, display_name, |staging| {
+        util::copy_dir_recursive(skill_path, staging)?;
+        report = apply_update(staging, &source_path, &latest, strategy)?;
+        Ok(())
+    })?;
+    print_merge_report(display_name, &report, strategy);
+
+    if scope == "local" {
+        let skill_name = skill_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let group = remote_skill_group(&latest.path);
+        let locked_name = if group.is_empty() {
+            skill_name
+        } else {
+            format!("{}/{}", group, skill_name)
+        };
+        lock_skill(skill_path, &locked_name, agent, &latest)?;
+    }
+
+    let from = installed
+        .commit
+        .as_deref()
+        .map(remote::short_commit)
+        .unwrap_or("unknown");
+    let to = latest
+        .commit
+        .as_deref()
+        .map(remote::short_commit)
+        .unwrap_or(&latest.git_ref);
+    if latest.transport.local_path().is_none() {
+        ui::success(&format!(
+            "Updated '{}' ({}): {} → {}",
+            display_name, scope, from, to
+        ));
+    } else {
+        ui::success(&format!(
+            "Updated '{}' ({}) from {}",
+            display_name,
+            scope,
+            latest.repo_label()
+        ));
+    }
+    Ok(true)
+}
+
+/// Report local edits kept or merged by an update, and any conflicts.
+fn print_merge_report(
+    display_name: &str,
+    report: &remote::MergeReport,
+    strategy: remote::MergeStrategy,
+) {
+    if !report.kept.is_empty() {
+        ui::info(&format!("Kept local edits in: {}", report.kept.join(", ")));
+    }
+    if !report.merged.is_empty() {
+        ui::info(&format!("Merged local and upstream edits in: {}", report.merged.join(", ")));
+    }
+    if report.conflicts.is_empty() {
+        return;
+    }
+    let files = report.conflicts.join(", ");
+    match strategy {
+        remote::MergeStrategy::Merge => {
+            ui::warn(&format!("Conflicts in '{}': {}", display_name, files));
+            ui::hint("Resolve the conflict markers by hand; --ours / --theirs pick a side instead");
+        }
+        remote::MergeStrategy::Ours => {
+            ui::warn(&format!("Kept local version of conflicting files: {}", files))
+        }
+        remote::MergeStrategy::Theirs => {
+            ui::warn(&format!("Took upstream version of conflicting files: {}", files))
+        }
+    }
 }
 
 fn list_profiles_display(json: bool) -> Result<()> {
     let source_dir = config::find_source_dir()
         .or_else(config::find_cwd_source_dir)
         .context(config::source_dir_hint())?;
-    let profiles = config::list_profiles(&source_dir);
+    let profiles = config::list_profiles(&source_dir)?;
 
     if json {
         let entries: Vec<serde_json::Value> = profiles
@@ -1429,7 +2821,7 @@ fn list_profiles_display(json: bool) -> Result<()> {
     }
 
     let mut table = ui::table::new_table();
-    table.set_header(&["Profile", "Description", "Skills"]);
+    table.set_header(["Profile", "Description", "Skills"]);
     for (name, desc, count) in &profiles {
         ui::table::add_row(&mut table, &[name, desc, &count.to_string()]);
     }
@@ -1441,7 +2833,7 @@ fn list_profiles_display(json: bool) -> Result<()> {
 
 // --- Helpers ---
 
-fn find_skill_in_source(source_dir: &Path, name: &str) -> Option<PathBuf> {
+pub fn find_skill_in_source(source_dir: &Path, name: &str) -> Option<PathBuf> {
     for group in config::skill_groups(source_dir) {
         let path = source_dir.join(&group).join(name);
         if path.is_dir() && path.join("SKILL.md").exists() {
@@ -1605,7 +2997,7 @@ fn read_skill_description(path: &Path) -> String {
     String::new()
 }
 
-fn truncate_description(desc: &str) -> String {
+pub fn truncate_description(desc: &str) -> String {
     let trimmed = desc.trim();
     if trimmed.chars().count() > 80 {
         let truncated: String = trimmed.chars().take(77).collect();
@@ -1677,7 +3069,7 @@ fn print_grouped_installed(local_dir: &Path, global_dir: &Path) {
     // Group by group name, extract skill name from key
     let mut groups: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
     for (key, (group, scope, _desc)) in &seen {
-        let skill_name = key.split('/').last().unwrap_or(key).to_string();
+        let skill_name = key.split('/').next_back().unwrap_or(key).to_string();
         groups
             .entry(group.clone())
             .or_default()
@@ -1710,7 +3102,7 @@ fn print_grouped_installed(local_dir: &Path, global_dir: &Path) {
 
 fn print_flat(entries: &[serde_json::Value]) {
     let mut table = ui::table::new_table();
-    table.set_header(&["Skill", "Scope", "Description"]);
+    table.set_header(["Skill", "Scope", "Description"]);
     for entry in entries {
         let name = entry["name"].as_str().unwrap_or("");
         let scope = entry["scope"].as_str().unwrap_or("");
@@ -1722,7 +3114,14 @@ fn print_flat(entries: &[serde_json::Value]) {
 
 #[cfg(test)]
 mod tests {
-    use super::{remote_skill_group, skills_named};
+    use super::{
+        apply_update, check_locked_hash, default_skill_body, find_installed_skill,
+        find_virtual_group_skills, infer_skill_group, install_copy, installed_skills, lock_entry,
+        remote_skill_group, resolve_requires, resolve_requires_in, skill_markdown, skills_named,
+    };
+    use crate::config::{self, SkillAgent};
+    use crate::remote;
+    use std::fs;
 
     #[test]
     fn remote_path_preserves_immediate_parent_as_group() {
@@ -1745,4 +3144,134 @@ mod tests {
             vec![("common".to_string(), "korean-editor".to_string())]
         );
     }
+
+    #[test]
+    fn installed_skills_reads_grouped_and_flat_layouts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let library = tmp.path().join("library/development/git-helper");
+        fs::create_dir_all(&library).unwrap();
+        fs::write(library.join("SKILL.md"), "---\nname: git-helper\n---\n").unwrap();
+
+        let claude = tmp.path().join("claude");
+        fs::create_dir_all(claude.join("common/korean-editor")).unwrap();
+        fs::write(claude.join("common/korean-editor/SKILL.md"), "").unwrap();
+        let codex = tmp.path().join("codex");
+        fs::create_dir_all(&codex).unwrap();
+        std::os::unix::fs::symlink(&library, codex.join("git-helper")).unwrap();
+
+        let grouped = installed_skills(&claude);
+        assert_eq!(grouped.len(), 1);
+        assert_eq!((grouped[0].0.as_str(), grouped[0].1.as_str()), ("common", "korean-editor"));
+
+        let flat = installed_skills(&codex);
+        assert_eq!(flat[0].0, "");
+        assert_eq!(infer_skill_group(&flat[0].2), "development");
+    }
+
+    #[test]
+    fn flat_agents_install_list_and_uninstall_without_group_dirs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let library = tmp.path().join("library/development/git-helper");
+        fs::create_dir_all(&library).unwrap();
+        fs::write(library.join("SKILL.md"), "---\nname: git-helper\n---\n").unwrap();
+
+        for agent in [SkillAgent::Gemini, SkillAgent::OpenCode, SkillAgent::Cursor] {
+            let target = tmp.path().join(agent.to_string());
+            fs::create_dir_all(&target).unwrap();
+            let dest = config::skill_destination(&target, "development", "git-helper", agent);
+            std::os::unix::fs::symlink(&library, &dest).unwrap();
+            assert_eq!(dest, target.join("git-helper"));
+            assert!(!target.join("development").exists());
+
+            let listed = installed_skills(&target);
+            assert_eq!(listed.len(), 1);
+            assert_eq!((listed[0].0.as_str(), listed[0].1.as_str()), ("", "git-helper"));
+            assert_eq!(infer_skill_group(&listed[0].2), "development");
+
+            // Uninstall resolves both the skill name and its inferred group
+            assert_eq!(find_installed_skill(&target, "git-helper"), Some(dest.clone()));
+            assert_eq!(find_virtual_group_skills(&target, "development"), vec![dest]);
+        }
+    }
+
+    #[test]
+    fn lock_after_update_keeps_upstream_hash() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let upstream = |dir: &str, body: &str| {
+            let path = tmp.path().join(dir);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("SKILL.md"), "---\nname: helper\n---\n").unwrap();
+            fs::write(path.join("notes.md"), body).unwrap();
+            path
+        };
+        let v1 = upstream("v1", "one\n");
+        let v2 = upstream("v2", "two\n");
+        let spec = remote::RemoteSpec {
+            owner: "acme".to_string(),
+            repo: "skills".to_string(),
+            path: "dev/helper".to_string(),
+            git_ref: "main".to_string(),
+            ..Default::default()
+        };
+
+        let dest = tmp.path().join("installed/helper");
+        install_copy(&v1, &dest, &spec, false, "helper").unwrap();
+        fs::write(dest.join("local.md"), "mine\n").unwrap();
+        apply_update(&dest, &v2, &spec, remote::MergeStrategy::Merge).unwrap();
+        assert!(dest.join("local.md").exists());
+
+        // A locked reinstall elsewhere fetches v2 and must match the entry
+        let entry = lock_entry(&dest, "dev/helper", SkillAgent::Claude, &spec).unwrap();
+        check_locked_hash(&entry, &spec, &v2).unwrap();
+        assert!(check_locked_hash(&entry, &spec, &v1).is_err());
+        assert_eq!(remote::upstream_hash(&dest).unwrap(), entry.hash);
+    }
+
+    #[test]
+    fn scaffolded_skill_passes_lint() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("dev/git-helper");
+        fs::create_dir_all(&dir).unwrap();
+        let body = default_skill_body("git-helper");
+        let markdown = skill_markdown("git-helper", "Commit: helper", &body).unwrap();
+        fs::write(dir.join("SKILL.md"), markdown).unwrap();
+
+        assert!(crate::cmd::lint::lint_skill(&dir).is_empty());
+    }
+
+    #[test]
+    fn requires_resolve_transitively_and_detect_cycles() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let skill = |path: &str, requires: &str| {
+            let dir = tmp.path().join(path);
+            fs::create_dir_all(&dir).unwrap();
+            let name = path.rsplit('/').next().unwrap();
+            let content = format!("---\nname: {}\nrequires: [{}]\n---\n", name, requires);
+            fs::write(dir.join("SKILL.md"), content).unwrap();
+        };
+        skill("dev/app", "context/static-index, helper");
+        skill("dev/helper", "context/static-index");
+        skill("context/static-index", "");
+
+        let pair = |g: &str, n: &str| (g.to_string(), n.to_string());
+        assert_eq!(
+            resolve_requires(tmp.path(), &[pair("dev", "app")]).unwrap(),
+            vec![pair("context", "static-index"), pair("dev", "helper"), pair("dev", "app")]
+        );
+
+        // A skill fetched at another version brings that version's requires
+        skill("v1/app", "context/static-index");
+        let pinned = [(pair("dev", "app"), tmp.path().join("v1/app"))].into_iter().collect();
+        assert_eq!(
+            resolve_requires_in(tmp.path(), &[pair("dev", "app")], &pinned).unwrap(),
+            vec![pair("context", "static-index"), pair("dev", "app")]
+        );
+
+        skill("context/static-index", "dev/app");
+        let err = resolve_requires(tmp.path(), &[pair("dev", "app")]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Dependency cycle: dev/app → context/static-index → dev/app"
+        );
+    }
 }
@@ -0,0 +1,234 @@
+use crate::{config, remote, ui, util};
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use std::fs;
+use std::path::Path;
+
+#[derive(Subcommand)]
+pub enum SourceAction {
+    /// Register a skills library (local directory or remote spec)
+    Add {
+        /// Name used in qualified skill names (<name>:group/skill)
+        name: String,
+        /// Local directory, or [host/]owner/repo[/path][@ref] to mirror
+        location: String,
+        /// Insert at this priority (1 = searched first); defaults to last
+        #[arg(long)]
+        priority: Option<usize>,
+    },
+    /// List registered sources in priority order
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Unregister a source and delete its mirror
+    Remove {
+        /// Source name
+        name: String,
+    },
+    /// Move a source to a new priority (1 = searched first)
+    Priority {
+        /// Source name
+        name: String,
+        /// New position
+        position: usize,
+    },
+    /// Re-download remote sources
+    Update {
+        /// Only this source
+        name: Option<String>,
+    },
+}
+
+pub fn execute(action: SourceAction) -> Result<()> {
+    match action {
+        SourceAction::Add {
+            name,
+            location,
+            priority,
+        } => add(&name, &location, priority),
+        SourceAction::List { json } => list(json),
+        SourceAction::Remove { name } => remove(&name),
+        SourceAction::Priority { name, position } => set_priority(&name, position),
+        SourceAction::Update { name } => update(name.as_deref()),
+    }
+}
+
+fn add(name: &str, location: &str, priority: Option<usize>) -> Result<()> {
+    util::validate_name(name)?;
+    if name == config::DEFAULT_SOURCE || name.contains(':') {
+        bail!("'{}' cannot be used as a source name", name);
+    }
+    let mut sources = config::load_sources()?;
+    if sources.iter().any(|s| s.name == name) {
+        bail!("Source '{}' already exists. Remove it first: agt source remove {}", name, name);
+    }
+
+    let path = Path::new(location);
+    let source = if path.is_dir() {
+        let path = fs::canonicalize(path)?;
+        if !config::has_skill_groups(&path) {
+            bail!("No skill groups (group/skill/SKILL.md) in {}", path.display());
+        }
+        config::SkillSource {
+            name: name.to_string(),
+            path: Some(path),
+            url: None,
+        }
+    } else {
+        remote::parse_spec(location)?;
+        let source = config::SkillSource {
+            name: name.to_string(),
+            path: None,
+            url: Some(location.to_string()),
+        };
+        mirror(&source)?;
+        source
+    };
+
+    let index = priority
+        .map(|p| p.clamp(1, sources.len() + 1) - 1)
+        .unwrap_or(sources.len());
+    sources.insert(index, source);
+    config::save_sources(&sources)?;
+    ui::success(&format!("Added source '{}' (priority {})", name, index + 1));
+    Ok(())
+}
+
+fn list(json: bool) -> Result<()> {
+    // Registered sources, then the discovered library searched after them
+    let registered = config::load_sources()?;
+    let discovered = config::skill_sources();
+    let sources: Vec<&config::SkillSource> = registered
+        .iter()
+        .chain(discovered.iter().filter(|s| s.name == config::DEFAULT_SOURCE))
+        .collect();
+
+    if json {
+        let output: Vec<serde_json::Value> = sources
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "name": s.name,
+                    "location": s.location(),
+                    "remote": s.url.is_some(),
+                    "dir": s.dir().display().to_string(),
+                    "skills": skill_count(&s.dir()),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if sources.is_empty() {
+        ui::info("No sources registered and no local library found.");
+        ui::hint("Add one with: agt source add <name> <dir | owner/repo>");
+        return Ok(());
+    }
+
+    ui::section("Skill Sources");
+    let mut table = ui::table::new_table();
+    table.set_header(["#", "Name", "Location", "Skills"]);
+    for (i, source) in sources.iter().enumerate() {
+        let skills = if source.dir().is_dir() {
+            skill_count(&source.dir()).to_string()
+        } else {
+            "not fetched".to_string()
+        };
+        ui::table::add_row(&mut table, &[
+            &(i + 1).to_string(),
+            &source.name,
+            &source.location(),
+            &skills,
+        ]);
+    }
+    println!("{table}");
+    eprintln!();
+    eprintln!("  Qualify ambiguous names as <source>:group/skill");
+    Ok(())
+}
+
+fn remove(name: &str) -> Result<()> {
+    let mut sources = config::load_sources()?;
+    let index = position_of(&sources, name)?;
+    let source = sources.remove(index);
+    config::save_sources(&sources)?;
+    if source.url.is_some() {
+        util::remove_path(&source.dir())?;
+    }
+    ui::success(&format!("Removed source '{}'", name));
+    Ok(())
+}
+
+fn set_priority(name: &str, position: usize) -> Result<()> {
+    let mut sources = config::load_sources()?;
+    if position == 0 || position > sources.len() {
+        bail!("Priority must be between 1 and {}", sources.len());
+    }
+    let source = sources.remove(position_of(&sources, name)?);
+    sources.insert(position - 1, source);
+    config::save_sources(&sources)?;
+    ui::success(&format!("Source '{}' is now priority {}", name, position));
+    Ok(())
+}
+
+fn update(name: Option<&str>) -> Result<()> {
+    let sources = config::load_sources()?;
+    if let Some(name) = name {
+        position_of(&sources, name)?;
+    }
+    let mut updated = 0;
+    for source in &sources {
+        if name.is_some_and(|n| n != source.name) || source.url.is_none() {
+            continue;
+        }
+        mirror(source)?;
+        updated += 1;
+    }
+    if updated == 0 {
+        ui::info("No remote sources to update");
+    }
+    Ok(())
+}
+
+/// Download a remote source into its mirror directory, replacing the old copy.
+fn mirror(source: &config::SkillSource) -> Result<()> {
+    let url = source.url.as_deref().context("Not a remote source")?;
+    let spec = remote::pin(remote::parse_spec(url)?);
+    ui::info(&format!("Downloading {}...", spec));
+    let (_tmp_dir, root) = remote::fetch_dir(&spec)?;
+    if !config::has_skill_groups(&root) {
+        bail!("No skill groups (group/skill/SKILL.md) in {}", spec);
+    }
+    remote::verify_item(&spec, &root)?;
+
+    // Swap the mirror in whole, so installs linked into it never dangle
+    let dir = source.dir();
+    util::install_atomic(&dir, true, &source.name, |staging| {
+        util::copy_dir_recursive(&root, staging)?;
+        remote::write_metadata(staging, &spec)
+    })?;
+    ui::success(&format!(
+        "Mirrored '{}': {} skills at {}",
+        source.name,
+        skill_count(&dir),
+        remote::short_commit(spec.commit.as_deref().unwrap_or(&spec.git_ref))
+    ));
+    Ok(())
+}
+
+fn position_of(sources: &[config::SkillSource], name: &str) -> Result<usize> {
+    sources
+        .iter()
+        .position(|s| s.name == name)
+        .context(format!("No source named '{}' (see: agt source list)", name))
+}
+
+fn skill_count(dir: &Path) -> usize {
+    config::skill_groups(dir)
+        .iter()
+        .map(|group| config::skills_in_group(dir, group).len())
+        .sum()
+}
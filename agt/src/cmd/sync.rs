@@ -0,0 +1,565 @@
+use crate::cmd::{hook, persona, skill, team};
+use crate::{config, remote, ui, util};
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Where declared items are installed from.
+enum Source {
+    /// Local library checkout; items are symlinked as by `agt skill install`
+    Library(PathBuf),
+    /// `[sync] source` spec; items are copied and pinned in agt.lock
+    Remote {
+        spec: Box<remote::RemoteSpec>,
+        root: PathBuf,
+        verifier: remote::SourceVerifier,
+        _tmp_dir: TempDir,
+    },
+}
+
+impl Source {
+    fn load(sync: &config::SyncSpec) -> Result<Self> {
+        let Some(spec_str) = &sync.source else {
+            let dir = config::find_source_dir()
+                .or_else(config::find_cwd_source_dir)
+                .context(config::source_dir_hint())?;
+            return Ok(Source::Library(dir));
+        };
+
+        let spec = remote::pin(remote::parse_spec(spec_str)?);
+        if !spec.path.is_empty() {
+            bail!(
+                "[sync] source must name a whole repository, not a path: {}",
+                spec_str
+            );
+        }
+        ui::info(&format!("Fetching {}...", spec_label(&spec)));
+        let (_tmp_dir, root) = remote::fetch_dir(&spec)?;
+        let verifier = remote::SourceVerifier::load(&root, &spec.repo_label())?;
+        Ok(Source::Remote {
+            spec: Box::new(spec),
+            root,
+            verifier,
+            _tmp_dir,
+        })
+    }
+
+    fn root(&self) -> &Path {
+        match self {
+            Source::Library(dir) => dir,
+            Source::Remote { root, .. } => root,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Source::Library(dir) => dir.display().to_string(),
+            Source::Remote { spec, .. } => spec_label(spec),
+        }
+    }
+
+    /// Whether `dest` is already an install of `item` from this source.
+    fn is_current(&self, item: &Path, dest: &Path) -> bool {
+        match self {
+            Source::Library(_) => {
+                dest.is_symlink() && fs::canonicalize(dest).ok() == fs::canonicalize(item).ok()
+            }
+            Source::Remote { spec, root, .. } => {
+                let Ok(rel) = item_path(root, item) else {
+                    return false;
+                };
+                !dest.is_symlink()
+                    && remote::parse_metadata(dest).is_ok_and(|installed| {
+                        installed.repo_label() == spec.repo_label()
+                            && installed.git_ref == spec.git_ref
+                            && installed.commit == spec.commit
+                            && installed.path == rel
+                    })
+            }
+        }
+    }
+
+    /// Install `item` at `dest`, replacing what is there. Returns the spec
+    /// to record in agt.lock for copied items.
+    fn install(&self, item: &Path, dest: &Path) -> Result<Option<remote::RemoteSpec>> {
+        if let Source::Remote { verifier, .. } = self {
+            verifier.check(item)?;
+        }
+        let name = dest
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match self {
+            Source::Library(_) => {
+                util::install_atomic(dest, true, &name, |staging| {
+                    symlink(item, staging)
+                        .context(format!("Failed to create symlink {}", dest.display()))
+                })?;
+                Ok(None)
+            }
+            Source::Remote { spec, root, .. } => {
+                let item_spec = spec.with_path(item_path(root, item)?);
+                util::install_atomic(dest, true, &name, |staging| {
+                    // Single-file personas become <dest>/PERSONA.md
+                    persona::copy_persona(item, staging)?;
+                    remote::write_metadata(staging, &item_spec)
+                })?;
+                Ok(Some(item_spec))
+            }
+        }
+    }
+}
+
+/// Path of `item` within the fetched source, as recorded in `.remote-source`.
+fn item_path(root: &Path, item: &Path) -> Result<String> {
+    Ok(item
+        .strip_prefix(root)
+        .context("Item is outside the source")?
+        .to_string_lossy()
+        .replace('\\', "/"))
+}
+
+/// `repo@ref`, or just the path for local sources.
+fn spec_label(spec: &remote::RemoteSpec) -> String {
+    if spec.git_ref.is_empty() {
+        spec.repo_label()
+    } else {
+        format!("{}@{}", spec.repo_label(), spec.git_ref)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Install,
+    Replace,
+    Remove,
+}
+
+/// A pending change to one installed item.
+#[derive(Debug)]
+struct ItemChange {
+    change: Change,
+    name: String,
+    /// Item in the source; None for removals
+    item: Option<PathBuf>,
+    dest: PathBuf,
+}
+
+/// A declared item: (name, path in the source, install destination)
+type Declared = (String, PathBuf, PathBuf);
+
+#[derive(Default)]
+struct Plan {
+    skills: Vec<(config::SkillAgent, ItemChange)>,
+    personas: Vec<ItemChange>,
+    hooks: Vec<String>,
+    teams: Vec<ItemChange>,
+    conflicts: Vec<String>,
+}
+
+impl Plan {
+    fn len(&self) -> usize {
+        self.skills.len() + self.personas.len() + self.hooks.len() + self.teams.len()
+    }
+}
+
+/// Bring the project in line with the `[sync]` section of its agt.toml.
+pub fn execute(check: bool) -> Result<()> {
+    let project = config::project_root();
+    let manifest = config::parse_manifest(&project)?
+        .context(format!("No agt.toml found in {}", project.display()))?;
+    let sync = &manifest.sync;
+    if sync.is_empty() {
+        bail!(
+            "agt.toml has no [sync] section\n  \
+             Declare skills under [sync.agents.claude] (skills, profiles) and \
+             personas, hooks and teams under [sync]."
+        );
+    }
+
+    let source = Source::load(sync)?;
+    let library = config::find_source_dir()
+        .or_else(config::find_cwd_source_dir)
+        .and_then(|dir| fs::canonicalize(dir).ok());
+    let plan = build_plan(sync, &source, library.as_deref())?;
+
+    print_plan(&plan, &source);
+    for conflict in &plan.conflicts {
+        ui::warn(conflict);
+    }
+
+    let count = plan.len();
+    if count == 0 {
+        ui::success("Project is in sync with agt.toml");
+        return Ok(());
+    }
+    if check {
+        bail!(
+            "{} change(s) needed to match agt.toml; run `agt sync` to apply",
+            count
+        );
+    }
+    apply(&plan, &source)
+}
+
+fn build_plan(sync: &config::SyncSpec, source: &Source, library: Option<&Path>) -> Result<Plan> {
+    let mut plan = Plan::default();
+    let root = source.root();
+
+    for (agent, declared) in sync.agents()? {
+        let target_dir = config::skill_target(false, agent);
+        let items: Vec<Declared> = declared_skills(declared, root)?
+            .into_iter()
+            .map(|(group, name)| {
+                let item = root.join(&group).join(&name);
+                let dest = config::skill_destination(&target_dir, &group, &name, agent);
+                (format!("{}/{}", group, name), item, dest)
+            })
+            .collect();
+        let installed = managed_skills(&target_dir, library);
+        for change in plan_items(&items, &installed, source, library, &mut plan.conflicts) {
+            plan.skills.push((agent, change));
+        }
+    }
+
+    if let Some(names) = &sync.personas {
+        let persona_lib = config::persona_library(root);
+        let persona_dir = config::local_persona_target();
+        let mut personas = Vec::new();
+        for name in names {
+            util::validate_name(name)?;
+            let item = persona::find_in_library(&persona_lib, name)?;
+            personas.push((name.clone(), item, persona_dir.join(name)));
+        }
+        let installed: Vec<(String, PathBuf)> = visible_entries(&persona_dir)
+            .into_iter()
+            .filter(|(_, path)| is_managed(path, library))
+            .collect();
+        plan.personas = plan_items(&personas, &installed, source, library, &mut plan.conflicts);
+    }
+
+    // Hooks (user-global) and teams are install-only: undeclared ones stay
+    for name in &sync.hooks {
+        if !hook::is_installed(name)? {
+            plan.hooks.push(name.clone());
+        }
+    }
+
+    let teams_dir = config::project_root().join(".claude/teams");
+    for name in &sync.teams {
+        if team::find_template_file(&teams_dir, name).is_some() {
+            continue;
+        }
+        let item = team::find_template_file(&root.join("teams"), name).context(format!(
+            "Team template '{}' not found in {}",
+            name,
+            source.label()
+        ))?;
+        let file_name = item.file_name().context("Invalid team template path")?;
+        plan.teams.push(ItemChange {
+            change: Change::Install,
+            name: name.clone(),
+            dest: teams_dir.join(file_name),
+            item: Some(item),
+        });
+    }
+
+    Ok(plan)
+}
+
+/// Skills declared for one agent, resolved against the source as (group, name).
+fn declared_skills(declared: &config::AgentSync, root: &Path) -> Result<BTreeSet<(String, String)>> {
+    let mut skills = BTreeSet::new();
+    for profile in &declared.profiles {
+        skills.extend(config::resolve_profile(profile, root)?.skills);
+    }
+
+    for entry in &declared.skills {
+        let (group, name) = match entry.split_once('/') {
+            Some((group, name)) => (group.to_string(), name.to_string()),
+            None => {
+                util::validate_name(entry)?;
+                let path = skill::find_skill_in_source(root, entry)
+                    .context(format!("Skill '{}' not found in source", entry))?;
+                let group = path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .context("Invalid skill path")?;
+                (group.to_string_lossy().to_string(), entry.clone())
+            }
+        };
+        util::validate_name(&group)?;
+        util::validate_name(&name)?;
+        if !root.join(&group).join(&name).join("SKILL.md").is_file() {
+            bail!("Skill '{}' not found in source", entry);
+        }
+        skills.insert((group, name));
+    }
+    Ok(skills)
+}
+
+/// Diff declared items against installed agt-managed ones. Unmanaged items
+/// in the way are reported as conflicts and never touched.
+fn plan_items(
+    declared: &[Declared],
+    installed: &[(String, PathBuf)],
+    source: &Source,
+    library: Option<&Path>,
+    conflicts: &mut Vec<String>,
+) -> Vec<ItemChange> {
+    let mut changes = Vec::new();
+    for (name, item, dest) in declared {
+        let change = if !dest.exists() && !dest.is_symlink() {
+            Change::Install
+        } else if source.is_current(item, dest) {
+            continue;
+        } else if is_managed(dest, library) {
+            Change::Replace
+        } else {
+            conflicts.push(format!(
+                "'{}' exists at {} but was not installed by agt; left as is",
+                name,
+                dest.display()
+            ));
+            continue;
+        };
+        changes.push(ItemChange {
+            change,
+            name: name.clone(),
+            item: Some(item.clone()),
+            dest: dest.clone(),
+        });
+    }
+
+    let wanted: HashSet<&Path> = declared.iter().map(|(_, _, dest)| dest.as_path()).collect();
+    for (name, path) in installed {
+        if !wanted.contains(path.as_path()) {
+            changes.push(ItemChange {
+                change: Change::Remove,
+                name: name.clone(),
+                item: None,
+                dest: path.clone(),
+            });
+        }
+    }
+    changes
+}
+
+/// Whether `path` was installed by agt: a copy carrying `.remote-source`
+/// metadata, or a symlink into the local library.
+fn is_managed(path: &Path, library: Option<&Path>) -> bool {
+    if path.is_symlink() {
+        return library.is_some_and(|lib| {
+            fs::canonicalize(path).is_ok_and(|target| target.starts_with(lib))
+        });
+    }
+    path.join(".remote-source").is_file()
+}
+
+/// agt-managed skills in an agent's skill directory, including one level of groups.
+fn managed_skills(target_dir: &Path, library: Option<&Path>) -> Vec<(String, PathBuf)> {
+    let mut found = Vec::new();
+    for (name, path) in visible_entries(target_dir) {
+        if is_managed(&path, library) {
+            found.push((name, path));
+        } else if path.is_dir() && !path.is_symlink() && !path.join("SKILL.md").exists() {
+            for (child, child_path) in visible_entries(&path) {
+                if is_managed(&child_path, library) {
+                    found.push((format!("{}/{}", name, child), child_path));
+                }
+            }
+        }
+    }
+    found
+}
+
+fn visible_entries(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut entries: Vec<(String, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+        .filter(|(name, _)| !name.starts_with('.'))
+        .collect();
+    entries.sort();
+    entries
+}
+
+fn print_plan(plan: &Plan, source: &Source) {
+    ui::section(&format!("Sync from {}", source.label()));
+    if plan.len() == 0 {
+        eprintln!("  Nothing to do.");
+        eprintln!();
+        return;
+    }
+
+    let line = |change: Change, kind: &str, name: &str| {
+        let marker = match change {
+            Change::Install => "+".green().bold(),
+            Change::Replace => "~".yellow().bold(),
+            Change::Remove => "-".red().bold(),
+        };
+        eprintln!("  {} {:<8} {}", marker, kind, name);
+    };
+    for (agent, c) in &plan.skills {
+        line(c.change, "skill", &format!("{} ({})", c.name, agent));
+    }
+    for c in &plan.personas {
+        line(c.change, "persona", &c.name);
+    }
+    for name in &plan.hooks {
+        line(Change::Install, "hook", name);
+    }
+    for c in &plan.teams {
+        line(c.change, "team", &c.name);
+    }
+    eprintln!();
+}
+
+fn apply(plan: &Plan, source: &Source) -> Result<()> {
+    let mut failed = 0;
+    let mut report = |kind: &str, name: &str, result: Result<()>| {
+        if let Err(e) = result {
+            ui::warn(&format!("Failed to sync {} '{}': {:#}", kind, name, e));
+            failed += 1;
+        }
+    };
+
+    for (agent, c) in &plan.skills {
+        report("skill", &c.name, apply_skill(*agent, c, source));
+    }
+    for c in &plan.personas {
+        report("persona", &c.name, apply_persona(c, source));
+    }
+    for name in &plan.hooks {
+        report("hook", name, hook::install_named(name));
+    }
+    for c in &plan.teams {
+        let result = (|| -> Result<()> {
+            let item = c.item.as_deref().context("Missing team template")?;
+            if let Some(parent) = c.dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(item, &c.dest).context(format!("Failed to write {}", c.dest.display()))?;
+            Ok(())
+        })();
+        report("team", &c.name, result);
+    }
+    if !plan.personas.is_empty() {
+        persona::post_persona_install();
+    }
+
+    let count = |change: Change| {
+        plan.skills.iter().map(|(_, c)| c).chain(&plan.personas).chain(&plan.teams)
+            .filter(|c| c.change == change)
+            .count()
+    };
+    let installed = count(Change::Install) + plan.hooks.len();
+    ui::success(&format!(
+        "Synced: {} installed, {} replaced, {} removed, {} failed",
+        installed,
+        count(Change::Replace),
+        count(Change::Remove),
+        failed
+    ));
+    if count(Change::Remove) > 0 {
+        ui::hint("Removed items are backed up; restore one with agt skill/persona rollback <name>");
+    }
+    if failed > 0 {
+        bail!("{} item(s) could not be synced", failed);
+    }
+    Ok(())
+}
+
+fn apply_skill(agent: config::SkillAgent, c: &ItemChange, source: &Source) -> Result<()> {
+    let target_dir = config::skill_target(false, agent);
+    match &c.item {
+        Some(item) => match source.install(item, &c.dest)? {
+            Some(spec) => {
+                remote::write_base(&c.dest, item)?;
+                skill::lock_skill(&c.dest, &c.name, agent, &spec)
+            }
+            None => skill::forget_locked_skill(&target_dir, &c.dest, agent),
+        },
+        None => {
+            util::remove_to_backup(&c.dest)?;
+            if let Some(parent) = c.dest.parent() {
+                if parent != target_dir {
+                    let _ = fs::remove_dir(parent);
+                }
+            }
+            skill::forget_locked_skill(&target_dir, &c.dest, agent)
+        }
+    }
+}
+
+fn apply_persona(c: &ItemChange, source: &Source) -> Result<()> {
+    let spec = match &c.item {
+        Some(item) => source.install(item, &c.dest)?,
+        None => {
+            util::remove_to_backup(&c.dest)?;
+            None
+        }
+    };
+    match spec {
+        Some(spec) => persona::lock_persona(&c.dest, &c.name, &spec),
+        None if config::lockfile_path().exists() => {
+            config::update_lockfile(|lock| lock.remove_persona(&c.name))
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_skill(dir: &Path) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), "---\nname: x\n---\n").unwrap();
+    }
+
+    #[test]
+    fn test_plan_library_skills() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lib = fs::canonicalize(tmp.path()).unwrap().join("lib");
+        let target = tmp.path().join("project/.claude/skills");
+        for name in ["a", "b", "c", "d"] {
+            make_skill(&lib.join("dev").join(name));
+        }
+        fs::create_dir_all(target.join("dev")).unwrap();
+        // b: undeclared library link, d: declared and current
+        symlink(lib.join("dev/b"), target.join("dev/b")).unwrap();
+        symlink(lib.join("dev/d"), target.join("dev/d")).unwrap();
+        // Hand-written skills are never managed
+        make_skill(&target.join("dev/c"));
+        make_skill(&target.join("mine"));
+
+        let source = Source::Library(lib.clone());
+        let declared: Vec<Declared> = ["a", "c", "d"]
+            .iter()
+            .map(|name| {
+                (
+                    format!("dev/{}", name),
+                    lib.join("dev").join(name),
+                    target.join("dev").join(name),
+                )
+            })
+            .collect();
+        let installed = managed_skills(&target, Some(&lib));
+        assert_eq!(installed.len(), 2);
+
+        let mut conflicts = Vec::new();
+        let changes = plan_items(&declared, &installed, &source, Some(&lib), &mut conflicts);
+        let summary: Vec<(Change, &str)> =
+            changes.iter().map(|c| (c.change, c.name.as_str())).collect();
+        assert_eq!(summary, [(Change::Install, "dev/a"), (Change::Remove, "dev/b")]);
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].contains("dev/c"));
+    }
+}
@@ -155,7 +155,7 @@ fn list(json_output: bool) -> Result<()> {
     }
 
     let mut table = ui::table::new_table();
-    table.set_header(&["Name", "Description", "Teammates", "Tasks"]);
+    table.set_header(["Name", "Description", "Teammates", "Tasks"]);
     for (name, template) in &templates {
         ui::table::add_row(&mut table, &[
             name,
@@ -181,7 +181,7 @@ fn show(name: &str) -> Result<()> {
     ui::section(&template.name);
 
     let mut info = ui::table::new_table();
-    info.set_header(&["Property", "Value"]);
+    info.set_header(["Property", "Value"]);
     ui::table::add_row(&mut info, &["Description", &template.description]);
     ui::table::add_row(&mut info, &["Display mode", &template.teammate_mode]);
     ui::table::add_row(&mut info, &[
@@ -196,10 +196,10 @@ fn show(name: &str) -> Result<()> {
     if !template.teammates.is_empty() {
         println!("\n{}", "Teammates".bold());
         let mut t = ui::table::new_table();
-        t.set_header(&["#", "Role", "Description"]);
+        t.set_header(["#", "Role", "Description"]);
         for (i, m) in template.teammates.iter().enumerate() {
             ui::table::add_row_cells(&mut t, vec![
-                Cell::new(&(i + 1).to_string()),
+                Cell::new((i + 1).to_string()),
                 Cell::new(&m.role),
                 Cell::new(&m.description),
             ]);
@@ -210,11 +210,11 @@ fn show(name: &str) -> Result<()> {
     if !template.tasks.is_empty() {
         println!("\n{}", "Initial tasks".bold());
         let mut t = ui::table::new_table();
-        t.set_header(&["#", "Title", "Assignee"]);
+        t.set_header(["#", "Title", "Assignee"]);
         for (i, task) in template.tasks.iter().enumerate() {
             let assignee = task.assignee.as_deref().unwrap_or("unassigned");
             ui::table::add_row_cells(&mut t, vec![
-                Cell::new(&(i + 1).to_string()),
+                Cell::new((i + 1).to_string()),
                 Cell::new(&task.title),
                 Cell::new(assignee),
             ]);
@@ -225,7 +225,7 @@ fn show(name: &str) -> Result<()> {
     if !template.hooks.is_empty() {
         println!("\n{}", "Hooks".bold());
         let mut t = ui::table::new_table();
-        t.set_header(&["Event", "Type"]);
+        t.set_header(["Event", "Type"]);
         for (event, hooks) in &template.hooks {
             for h in hooks {
                 ui::table::add_row_cells(&mut t, vec![
@@ -581,6 +581,25 @@ fn load_templates_from_dir(dir: &Path, templates: &mut BTreeMap<String, TeamTemp
     }
 }
 
+/// Template file in `dir` defining team `name`: `<name>.yml`/`.yaml`, else
+/// any template whose `name:` matches.
+pub fn find_template_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    for ext in ["yml", "yaml"] {
+        let path = dir.join(format!("{}.{}", name, ext));
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+    fs::read_dir(dir).ok()?.flatten().map(|e| e.path()).find(|path| {
+        let ext = path.extension().and_then(|e| e.to_str());
+        (ext == Some("yml") || ext == Some("yaml"))
+            && fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_yaml::from_str::<TeamTemplate>(&content).ok())
+                .is_some_and(|template| template.name == name)
+    })
+}
+
 fn load_template(name: &str) -> Result<TeamTemplate> {
     let templates = load_all_templates()?;
     templates
@@ -0,0 +1,187 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LOCK_FILE: &str = "agt.lock";
+const LOCK_VERSION: u32 = 1;
+
+/// `agt.lock` — the exact remote skills and personas installed into a project.
+///
+/// Only project-scope remote installs are recorded. Library symlinks follow the
+/// local checkout and global installs belong to the user, not the project.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Lockfile {
+    #[serde(default = "lock_version")]
+    pub version: u32,
+    #[serde(default, rename = "skill", skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<LockEntry>,
+    #[serde(default, rename = "persona", skip_serializing_if = "Vec::is_empty")]
+    pub personas: Vec<LockEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockEntry {
+    /// Installed name: "group/skill" for skills, the persona name for personas
+    pub name: String,
+    /// Agent whose skill directory holds the install (skills only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Remote spec the item was installed from: owner/repo/path@ref
+    pub source: String,
+    /// Commit the ref resolved to at install time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Content hash of the upstream files installed (see `util::hash_dir`)
+    pub hash: String,
+}
+
+fn lock_version() -> u32 {
+    LOCK_VERSION
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCK_VERSION,
+            skills: Vec::new(),
+            personas: Vec::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Insert or replace a skill entry, keyed by name and agent.
+    pub fn upsert_skill(&mut self, entry: LockEntry) {
+        upsert(&mut self.skills, entry);
+    }
+
+    /// Insert or replace a persona entry, keyed by name.
+    pub fn upsert_persona(&mut self, entry: LockEntry) {
+        upsert(&mut self.personas, entry);
+    }
+
+    /// Remove a skill entry. `name` may omit the group, as flat agent layouts do.
+    pub fn remove_skill(&mut self, name: &str, agent: &str) {
+        self.skills.retain(|e| {
+            let same_name = e.name == name || e.name.rsplit('/').next() == Some(name);
+            !(same_name && e.agent.as_deref() == Some(agent))
+        });
+    }
+
+    pub fn remove_persona(&mut self, name: &str) {
+        self.personas.retain(|e| e.name != name);
+    }
+}
+
+fn upsert(entries: &mut Vec<LockEntry>, entry: LockEntry) {
+    entries.retain(|e| !(e.name == entry.name && e.agent == entry.agent));
+    entries.push(entry);
+    entries.sort_by(|a, b| (&a.name, &a.agent).cmp(&(&b.name, &b.agent)));
+}
+
+/// Path of the project lockfile
+pub fn lockfile_path() -> PathBuf {
+    super::project_root().join(LOCK_FILE)
+}
+
+/// Parse a lockfile. Returns None if the file doesn't exist.
+pub fn parse_lockfile(path: &Path) -> Result<Option<Lockfile>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+    let lock: Lockfile =
+        toml::from_str(&content).context(format!("Invalid {}", path.display()))?;
+    // Commits end up in cache paths and download URLs
+    for entry in lock.skills.iter().chain(&lock.personas) {
+        if let Some(commit) = entry.commit.as_deref().filter(|c| !crate::util::is_commit_sha(c)) {
+            bail!(
+                "Invalid {}: '{}' has commit '{}', expected a 40-character SHA",
+                path.display(),
+                entry.name,
+                commit
+            );
+        }
+    }
+    Ok(Some(lock))
+}
+
+pub fn write_lockfile(path: &Path, lock: &Lockfile) -> Result<()> {
+    let content = toml::to_string(lock).context("Failed to serialize lockfile")?;
+    fs::write(path, format!("# Generated by agt. Do not edit by hand.\n{}", content))
+        .context(format!("Failed to write {}", path.display()))
+}
+
+/// Load the project lockfile, apply `f`, and write it back.
+pub fn update_lockfile(f: impl FnOnce(&mut Lockfile)) -> Result<()> {
+    let path = lockfile_path();
+    let mut lock = parse_lockfile(&path)?.unwrap_or_default();
+    f(&mut lock);
+    write_lockfile(&path, &lock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, agent: Option<&str>, hash: &str) -> LockEntry {
+        LockEntry {
+            name: name.to_string(),
+            agent: agent.map(str::to_string),
+            source: format!("jiunbae/agent-skills/{}@main", name),
+            commit: Some("0123456789abcdef0123456789abcdef01234567".to_string()),
+            hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_lockfile_roundtrip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(LOCK_FILE);
+
+        let mut lock = Lockfile::default();
+        lock.upsert_skill(entry("development/git-commit-pr", Some("claude"), "sha256:aa"));
+        lock.upsert_persona(entry("security-reviewer", None, "sha256:bb"));
+        write_lockfile(&path, &lock).unwrap();
+
+        let parsed = parse_lockfile(&path).unwrap().unwrap();
+        assert_eq!(parsed.version, LOCK_VERSION);
+        assert_eq!(parsed.skills, lock.skills);
+        assert_eq!(parsed.personas, lock.personas);
+    }
+
+    #[test]
+    fn test_lockfile_rejects_bad_commit() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(LOCK_FILE);
+        let mut lock = Lockfile::default();
+        let mut bad = entry("development/git-commit-pr", Some("claude"), "sha256:aa");
+        bad.commit = Some("../../../tmp/x".to_string());
+        lock.upsert_skill(bad);
+        write_lockfile(&path, &lock).unwrap();
+        assert!(parse_lockfile(&path).is_err());
+    }
+
+    #[test]
+    fn test_lockfile_missing() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert!(parse_lockfile(&tmp.path().join(LOCK_FILE)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_upsert_replaces_same_agent_only() {
+        let mut lock = Lockfile::default();
+        lock.upsert_skill(entry("context/static-index", Some("claude"), "sha256:old"));
+        lock.upsert_skill(entry("context/static-index", Some("codex"), "sha256:codex"));
+        lock.upsert_skill(entry("context/static-index", Some("claude"), "sha256:new"));
+
+        assert_eq!(lock.skills.len(), 2);
+        assert_eq!(lock.skills[0].agent.as_deref(), Some("claude"));
+        assert_eq!(lock.skills[0].hash, "sha256:new");
+
+        lock.remove_skill("static-index", "codex");
+        assert_eq!(lock.skills.len(), 1);
+    }
+}
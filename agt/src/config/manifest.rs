@@ -1,14 +1,19 @@
 use anyhow::{bail, Context, Result};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use super::SkillAgent;
+
 const MANIFEST_FILE: &str = "agt.toml";
 
 #[derive(Debug, Deserialize, Default)]
 pub struct Manifest {
     #[serde(default)]
     pub setup: Setup,
+    #[serde(default)]
+    pub sync: SyncSpec,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -25,6 +30,54 @@ pub struct SetupCopy {
     pub strategy: String,
 }
 
+/// `[sync]` — what a project needs installed, applied by `agt sync`.
+#[derive(Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SyncSpec {
+    /// Spec of the repository to install from; the local library when unset
+    pub source: Option<String>,
+    /// Project personas; when omitted, installed personas are left alone
+    pub personas: Option<Vec<String>>,
+    #[serde(default)]
+    pub hooks: Vec<String>,
+    #[serde(default)]
+    pub teams: Vec<String>,
+    /// Skills per agent (`[sync.agents.claude]`); other agents are left alone
+    #[serde(default)]
+    pub agents: BTreeMap<String, AgentSync>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AgentSync {
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    /// `name` or `group/name`
+    #[serde(default)]
+    pub skills: Vec<String>,
+}
+
+impl SyncSpec {
+    pub fn is_empty(&self) -> bool {
+        self.personas.is_none()
+            && self.hooks.is_empty()
+            && self.teams.is_empty()
+            && self.agents.is_empty()
+    }
+
+    /// Declared agents with their skill sets, in agent order.
+    pub fn agents(&self) -> Result<Vec<(SkillAgent, &AgentSync)>> {
+        let mut agents = Vec::new();
+        for (name, declared) in &self.agents {
+            let agent = <SkillAgent as clap::ValueEnum>::from_str(name, true)
+                .map_err(|_| anyhow::anyhow!("Unknown agent '{}' in [sync.agents]", name))?;
+            agents.push((agent, declared));
+        }
+        agents.sort_by_key(|(agent, _)| agent.to_string());
+        Ok(agents)
+    }
+}
+
 fn default_strategy() -> String {
     "merge".to_string()
 }
@@ -46,6 +99,7 @@ pub fn parse_manifest(dir: &Path) -> Result<Option<Manifest>> {
     for rule in &manifest.setup.copy {
         validate_copy_rule(rule)?;
     }
+    manifest.sync.agents()?;
 
     Ok(Some(manifest))
 }
@@ -128,6 +182,42 @@ to = "~/.agents/personas"
         assert_eq!(manifest.setup.copy[1].strategy, "merge"); // default
     }
 
+    #[test]
+    fn test_parse_sync_section() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("agt.toml"),
+            r#"
+[sync]
+source = "jiunbae/agent-skills@v1"
+personas = ["security-reviewer"]
+teams = ["debug"]
+
+[sync.agents.claude]
+profiles = ["core"]
+skills = ["development/git-commit-pr"]
+
+[sync.agents.codex]
+skills = ["planning"]
+"#,
+        )
+        .unwrap();
+
+        let manifest = parse_manifest(tmp.path()).unwrap().unwrap();
+        let sync = &manifest.sync;
+        assert_eq!(sync.source.as_deref(), Some("jiunbae/agent-skills@v1"));
+        assert!(sync.hooks.is_empty());
+        let agents = sync.agents().unwrap();
+        assert_eq!(agents[0].0, SkillAgent::Claude);
+        assert_eq!(agents[0].1.profiles, ["core"]);
+        assert_eq!(agents[1].1.skills, ["planning"]);
+
+        fs::write(tmp.path().join("agt.toml"), "[sync.agents.vim]\nskills = []\n").unwrap();
+        assert!(parse_manifest(tmp.path()).is_err());
+        fs::write(tmp.path().join("agt.toml"), "[sync]\nskill = []\n").unwrap();
+        assert!(parse_manifest(tmp.path()).is_err());
+    }
+
     #[test]
     fn test_parse_manifest_missing() {
         let tmp = tempfile::TempDir::new().unwrap();
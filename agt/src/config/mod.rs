@@ -1,7 +1,13 @@
+mod lockfile;
 mod manifest;
 mod paths;
 mod profiles;
+mod settings;
+mod sources;
 
+pub use lockfile::*;
 pub use manifest::*;
 pub use paths::*;
 pub use profiles::*;
+pub use settings::*;
+pub use sources::*;
@@ -2,11 +2,16 @@ use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SkillAgent {
     #[default]
     Claude,
     Codex,
+    Gemini,
+    #[value(name = "opencode")]
+    OpenCode,
+    Cursor,
 }
 
 impl fmt::Display for SkillAgent {
@@ -14,6 +19,9 @@ impl fmt::Display for SkillAgent {
         match self {
             Self::Claude => write!(f, "claude"),
             Self::Codex => write!(f, "codex"),
+            Self::Gemini => write!(f, "gemini"),
+            Self::OpenCode => write!(f, "opencode"),
+            Self::Cursor => write!(f, "cursor"),
         }
     }
 }
@@ -36,45 +44,83 @@ const EXCLUDE_DIRS: &[&str] = &[
     "target",
 ];
 
-/// Find agt source directory.
-/// Priority: env var (cheapest) > walk up from exe > home dir fallbacks
-pub fn find_source_dir() -> Option<PathBuf> {
+/// A location `find_source_dir` considers, in priority order.
+#[derive(Debug, Clone)]
+pub struct SourceCandidate {
+    /// `AGT_DIR`, `AGENT_SKILLS_DIR`, `paths.library`, `executable` or `home`
+    pub origin: &'static str,
+    pub path: PathBuf,
+}
+
+impl SourceCandidate {
+    /// Set by an env var or the `paths.library` setting rather than discovered.
+    pub fn is_explicit(&self) -> bool {
+        self.origin.ends_with("_DIR") || self.origin == "paths.library"
+    }
+
+    /// Explicit paths only need to exist; discovered ones must hold skill groups.
+    pub fn is_valid(&self) -> bool {
+        if self.is_explicit() {
+            self.path.is_dir()
+        } else {
+            has_skill_groups(&self.path)
+        }
+    }
+}
+
+/// Candidates for the agt source directory.
+/// Priority: env var (cheapest) > `paths.library` setting > walk up from exe > home dir fallbacks
+pub fn source_dir_candidates() -> Vec<SourceCandidate> {
+    let mut candidates = Vec::new();
+
     // 1. Cheapest check: env var
-    if let Ok(env_dir) = std::env::var("AGT_DIR").or_else(|_| std::env::var("AGENT_SKILLS_DIR")) {
-        let p = PathBuf::from(env_dir);
-        if p.is_dir() {
-            return Some(p);
+    for var in ["AGT_DIR", "AGENT_SKILLS_DIR"] {
+        if let Ok(env_dir) = std::env::var(var) {
+            candidates.push(SourceCandidate {
+                origin: var,
+                path: PathBuf::from(env_dir),
+            });
+            break;
         }
     }
 
+    if let Some(library) = super::load_settings().ok().and_then(|s| s.paths.library) {
+        candidates.push(SourceCandidate {
+            origin: "paths.library",
+            path: library,
+        });
+    }
+
     // 2. Walk up from executable following symlinks
     if let Ok(exe) = std::env::current_exe() {
         let resolved = fs::canonicalize(&exe).unwrap_or(exe);
-        let mut dir = resolved.parent();
-        for _ in 0..5 {
-            match dir {
-                Some(d) => {
-                    if has_skill_groups(d) {
-                        return Some(d.to_path_buf());
-                    }
-                    dir = d.parent();
-                }
-                None => break,
-            }
+        for dir in resolved.ancestors().skip(1).take(5) {
+            candidates.push(SourceCandidate {
+                origin: "executable",
+                path: dir.to_path_buf(),
+            });
         }
     }
 
     // 3. Fallback: check common install locations
     if let Some(home) = dirs::home_dir() {
         for candidate in &[".agent-skills", ".agt", "agt"] {
-            let p = home.join(candidate);
-            if has_skill_groups(&p) {
-                return Some(p);
-            }
+            candidates.push(SourceCandidate {
+                origin: "home",
+                path: home.join(candidate),
+            });
         }
     }
 
-    None
+    candidates
+}
+
+/// Find agt source directory: the first valid `source_dir_candidates` entry.
+pub fn find_source_dir() -> Option<PathBuf> {
+    source_dir_candidates()
+        .into_iter()
+        .find(SourceCandidate::is_valid)
+        .map(|c| c.path)
 }
 
 /// Check if CWD (or its git root) is itself a skills source directory.
@@ -188,6 +234,11 @@ fn git_root() -> Option<PathBuf> {
     }
 }
 
+/// Project root: the enclosing git repository, or the current directory.
+pub fn project_root() -> PathBuf {
+    git_root().unwrap_or_else(|| PathBuf::from("."))
+}
+
 /// Skill target directories
 pub fn local_skill_target() -> PathBuf {
     git_root()
@@ -213,17 +264,38 @@ pub fn global_codex_skill_target() -> PathBuf {
         .join(".agents/skills")
 }
 
+/// `<project root>/<rel>`
+fn project_path(rel: &str) -> PathBuf {
+    git_root()
+        .map(|r| r.join(rel))
+        .unwrap_or_else(|| PathBuf::from(rel))
+}
+
+/// `~/<rel>`
+fn home_path(rel: &str) -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("~"))
+        .join(rel)
+}
+
 pub fn skill_target(global: bool, agent: SkillAgent) -> PathBuf {
     match (global, agent) {
         (false, SkillAgent::Claude) => local_skill_target(),
         (true, SkillAgent::Claude) => global_skill_target(),
         (false, SkillAgent::Codex) => local_codex_skill_target(),
         (true, SkillAgent::Codex) => global_codex_skill_target(),
+        (false, SkillAgent::Gemini) => project_path(".gemini/skills"),
+        (true, SkillAgent::Gemini) => home_path(".gemini/skills"),
+        (false, SkillAgent::OpenCode) => project_path(".opencode/skills"),
+        (true, SkillAgent::OpenCode) => home_path(".config/opencode/skills"),
+        (false, SkillAgent::Cursor) => project_path(".cursor/skills"),
+        (true, SkillAgent::Cursor) => home_path(".cursor/skills"),
     }
 }
 
-/// Claude supports grouped skill directories. Codex discovers direct children
-/// of `.agents/skills`, so Codex destinations must remain flat.
+/// Claude supports grouped skill directories. Codex, Gemini CLI, OpenCode and
+/// Cursor only discover direct children of their skill directory, so their
+/// destinations must remain flat.
 pub fn skill_destination(
     target_dir: &Path,
     group: &str,
@@ -266,6 +338,49 @@ pub fn claude_settings_path() -> PathBuf {
         .join(".claude/settings.json")
 }
 
+/// Download cache root: `$AGT_CACHE_DIR` or `paths.cache`, else the platform
+/// cache dir (`$XDG_CACHE_HOME/agt` or `~/.cache/agt` on Linux).
+pub fn cache_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var("AGT_CACHE_DIR") {
+        if !dir.is_empty() {
+            return PathBuf::from(dir);
+        }
+    }
+    if let Some(dir) = super::load_settings().ok().and_then(|s| s.paths.cache) {
+        return dir;
+    }
+    dirs::cache_dir()
+        .or_else(|| dirs::home_dir().map(|h| h.join(".cache")))
+        .unwrap_or_else(|| PathBuf::from("~/.cache"))
+        .join("agt")
+}
+
+/// User config directory: `$XDG_CONFIG_HOME/agt` or `~/.config/agt` on Linux.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+        .unwrap_or_else(|| PathBuf::from("~/.config"))
+        .join("agt")
+}
+
+/// Previous versions replaced by installs and updates: `$AGT_BACKUP_DIR` or
+/// `paths.backups`, else the platform data dir (`$XDG_DATA_HOME/agt/backups`
+/// or `~/.local/share/agt/backups`).
+pub fn backup_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var("AGT_BACKUP_DIR") {
+        if !dir.is_empty() {
+            return PathBuf::from(dir);
+        }
+    }
+    if let Some(dir) = super::load_settings().ok().and_then(|s| s.paths.backups) {
+        return dir;
+    }
+    dirs::data_dir()
+        .or_else(|| dirs::home_dir().map(|h| h.join(".local/share")))
+        .unwrap_or_else(|| PathBuf::from("~/.local/share"))
+        .join("agt/backups")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,4 +410,24 @@ mod tests {
             PathBuf::from("/tmp/skills/git-commit-pr")
         );
     }
+
+    #[test]
+    fn other_agents_use_flat_layout_in_own_directories() {
+        for (agent, local) in [
+            (SkillAgent::Gemini, ".gemini/skills"),
+            (SkillAgent::OpenCode, ".opencode/skills"),
+            (SkillAgent::Cursor, ".cursor/skills"),
+        ] {
+            assert!(skill_target(false, agent).ends_with(local));
+            assert_eq!(
+                skill_destination(Path::new("/tmp/skills"), "development", "git-commit-pr", agent),
+                PathBuf::from("/tmp/skills/git-commit-pr")
+            );
+        }
+        assert!(skill_target(true, SkillAgent::OpenCode).ends_with(".config/opencode/skills"));
+        assert_eq!(
+            <SkillAgent as clap::ValueEnum>::from_str("opencode", true).unwrap(),
+            SkillAgent::OpenCode
+        );
+    }
 }
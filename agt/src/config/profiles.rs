@@ -1,22 +1,36 @@
-use serde::Deserialize;
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ProfileDef {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description: String,
-    #[serde(default)]
+    /// Profiles whose skills this one starts from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub skills: Vec<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub groups: Vec<String>,
+    /// `group/name` skills, or whole groups, removed from the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// File the profile was read from; `None` for builtins
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
 }
 
 #[allow(dead_code)]
+#[derive(Debug)]
 pub struct ResolvedProfile {
     pub name: String,
     pub description: String,
     pub skills: Vec<(String, String)>, // (group, skill_name)
+    /// Version constraints from `group/name@^1.2` entries, keyed by `group/name`
+    pub versions: BTreeMap<String, String>,
 }
 
 fn builtin_profiles() -> BTreeMap<String, ProfileDef> {
@@ -34,70 +48,165 @@ fn builtin_profiles() -> BTreeMap<String, ProfileDef> {
                 "agents/background-planner".into(),
                 "agents/background-reviewer".into(),
             ],
-            groups: vec![],
+            ..Default::default()
         },
     );
     map
 }
 
-fn load_profiles_file(source_dir: &Path) -> Option<BTreeMap<String, ProfileDef>> {
+/// Parse a profile file. Empty files define no profiles.
+fn parse_profiles_file(path: &Path) -> anyhow::Result<BTreeMap<String, ProfileDef>> {
+    let content =
+        std::fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+    if content.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let mut profiles: BTreeMap<String, ProfileDef> = serde_yaml::from_str(&content)
+        .context(format!("Invalid profile file {}", path.display()))?;
+    for def in profiles.values_mut() {
+        def.source = Some(path.to_path_buf());
+    }
+    Ok(profiles)
+}
+
+/// Personal profiles: `~/.config/agt/profiles.yml`.
+pub fn user_profiles_path() -> PathBuf {
+    super::config_dir().join("profiles.yml")
+}
+
+/// Profiles shared with a project: `.agents/profiles.yml` at its root.
+pub fn project_profiles_path() -> PathBuf {
+    super::project_root().join(".agents/profiles.yml")
+}
+
+/// Add or replace profile `name` in the profile file at `path`.
+pub fn save_profile(path: &Path, name: &str, def: &ProfileDef, force: bool) -> anyhow::Result<()> {
+    let mut profiles = if path.is_file() {
+        parse_profiles_file(path)?
+    } else {
+        BTreeMap::new()
+    };
+    if profiles.contains_key(name) && !force {
+        bail!(
+            "Profile '{}' already exists in {} (use --force to replace it)",
+            name,
+            path.display()
+        );
+    }
+    profiles.insert(name.to_string(), def.clone());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = serde_yaml::to_string(&profiles)?;
+    std::fs::write(path, content).context(format!("Failed to write {}", path.display()))
+}
+
+/// Definition of profile `name` as `resolve_profile` would see it.
+pub fn find_profile(name: &str, source_dir: &Path) -> anyhow::Result<Option<ProfileDef>> {
+    Ok(available_profiles(source_dir)?.remove(name))
+}
+
+fn load_profiles_file(source_dir: &Path) -> anyhow::Result<Option<BTreeMap<String, ProfileDef>>> {
     let mut merged = BTreeMap::new();
 
-    // Try profiles.yml first (canonical name)
+    // profiles.yml first (canonical name)
     let canonical = source_dir.join("profiles.yml");
-    if let Ok(content) = std::fs::read_to_string(&canonical) {
-        if let Ok(profiles) = serde_yaml::from_str::<BTreeMap<String, ProfileDef>>(&content) {
-            merged.extend(profiles);
-        }
+    if canonical.is_file() {
+        merged.extend(parse_profiles_file(&canonical)?);
     }
 
-    // Also scan all *.yml files at root (repos may split profiles across files)
-    if let Ok(entries) = std::fs::read_dir(source_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().and_then(|e| e.to_str()) == Some("yml")
-                && path.file_name().unwrap_or_default() != "profiles.yml"
-            {
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    if let Ok(profiles) =
-                        serde_yaml::from_str::<BTreeMap<String, ProfileDef>>(&content)
-                    {
-                        merged.extend(profiles);
-                    }
-                }
-            }
+    // Also scan other root *.yml files (repos may split profiles across files),
+    // skipping dotfiles and unrelated YAML such as CI or docs configuration
+    let mut others: Vec<PathBuf> = std::fs::read_dir(source_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    path.extension().and_then(|e| e.to_str()) == Some("yml")
+                        && name != "profiles.yml"
+                        && !name.starts_with('.')
+                        && path.is_file()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    others.sort();
+    for path in others {
+        if looks_like_profiles(&path) {
+            merged.extend(parse_profiles_file(&path)?);
         }
     }
 
     if merged.is_empty() {
-        None
+        Ok(None)
     } else {
-        Some(merged)
+        Ok(Some(merged))
     }
 }
 
-fn available_profiles(source_dir: &Path) -> BTreeMap<String, ProfileDef> {
+/// Whether a YAML file is a profile map: every top-level value is a mapping
+/// and at least one lists `skills`, `groups` or `extends`.
+fn looks_like_profiles(path: &Path) -> bool {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return false;
+    };
+    let Ok(serde_yaml::Value::Mapping(map)) = serde_yaml::from_str(&content) else {
+        return false;
+    };
+    let mut defines_skills = false;
+    for value in map.values() {
+        let Some(def) = value.as_mapping() else {
+            return false;
+        };
+        defines_skills |= ["skills", "groups", "extends"]
+            .iter()
+            .any(|key| def.contains_key(*key));
+    }
+    defines_skills
+}
+
+fn available_profiles(source_dir: &Path) -> anyhow::Result<BTreeMap<String, ProfileDef>> {
     available_profiles_with_builtins(source_dir, true)
 }
 
 fn available_profiles_with_builtins(
     source_dir: &Path,
     include_builtins: bool,
-) -> BTreeMap<String, ProfileDef> {
+) -> anyhow::Result<BTreeMap<String, ProfileDef>> {
     let mut profiles = if include_builtins {
         builtin_profiles()
     } else {
         BTreeMap::new()
     };
-    if let Some(file_profiles) = load_profiles_file(source_dir) {
+    if let Some(file_profiles) = load_profiles_file(source_dir)? {
         for (name, def) in file_profiles {
             profiles.insert(name, def);
         }
     }
-    profiles
+    // User profiles override the library's, project profiles override both
+    for path in [user_profiles_path(), project_profiles_path()] {
+        if path.is_file() {
+            profiles.extend(parse_profiles_file(&path)?);
+        }
+    }
+    Ok(profiles)
 }
 
 pub fn resolve_profile(name: &str, source_dir: &Path) -> anyhow::Result<ResolvedProfile> {
+    let profiles = available_profiles(source_dir)?;
+    resolve_with(name, &profiles, source_dir, &mut Vec::new())
+}
+
+/// Resolve `name` against `profiles`, following `extends` first.
+/// `stack` holds the profiles being resolved, to report cycles.
+fn resolve_with(
+    name: &str,
+    profiles: &BTreeMap<String, ProfileDef>,
+    source_dir: &Path,
+    stack: &mut Vec<String>,
+) -> anyhow::Result<ResolvedProfile> {
     if name == "all" {
         let mut skills = Vec::new();
         for group in super::skill_groups(source_dir) {
@@ -109,10 +218,13 @@ pub fn resolve_profile(name: &str, source_dir: &Path) -> anyhow::Result<Resolved
             name: "all".to_string(),
             description: "All available skills".to_string(),
             skills,
+            versions: BTreeMap::new(),
         });
     }
 
-    let profiles = available_profiles(source_dir);
+    if stack.iter().any(|p| p == name) {
+        bail!("Profile cycle: {} → {}", stack.join(" → "), name);
+    }
     let def = profiles.get(name).ok_or_else(|| {
         let available: Vec<_> = profiles
             .keys()
@@ -127,17 +239,43 @@ pub fn resolve_profile(name: &str, source_dir: &Path) -> anyhow::Result<Resolved
     })?;
 
     let mut skills = Vec::new();
+    let mut versions = BTreeMap::new();
 
-    for spec in &def.skills {
-        if let Some((group, skill_name)) = spec.split_once('/') {
-            let pair = (group.to_string(), skill_name.to_string());
+    stack.push(name.to_string());
+    for parent in &def.extends {
+        let base = resolve_with(parent, profiles, source_dir, stack)?;
+        for pair in base.skills {
             if !skills.contains(&pair) {
                 skills.push(pair);
             }
         }
+        versions.extend(base.versions);
+    }
+    stack.pop();
+
+    for spec in &def.skills {
+        let spec = match spec.split_once('@') {
+            Some((entry, req)) => {
+                semver::VersionReq::parse(req).context(format!(
+                    "Invalid version constraint in profile '{}': {}",
+                    name, spec
+                ))?;
+                versions.insert(entry.to_string(), req.to_string());
+                entry
+            }
+            None => spec.as_str(),
+        };
+        let pair = profile_skill(name, spec, source_dir)?;
+        if !skills.contains(&pair) {
+            skills.push(pair);
+        }
     }
 
+    let groups = super::skill_groups(source_dir);
     for group in &def.groups {
+        if !groups.contains(group) {
+            bail!("Profile '{}' references unknown group '{}'", name, group);
+        }
         for skill in super::skills_in_group(source_dir, group) {
             let pair = (group.clone(), skill);
             if !skills.contains(&pair) {
@@ -146,34 +284,77 @@ pub fn resolve_profile(name: &str, source_dir: &Path) -> anyhow::Result<Resolved
         }
     }
 
+    for entry in &def.exclude {
+        if entry.contains('/') {
+            let pair = profile_skill(name, entry, source_dir)?;
+            skills.retain(|p| *p != pair);
+            versions.remove(entry);
+        } else if groups.contains(entry) {
+            skills.retain(|(group, _)| group != entry);
+            versions.retain(|key, _| !key.starts_with(&format!("{}/", entry)));
+        } else {
+            bail!("Profile '{}' excludes unknown group '{}'", name, entry);
+        }
+    }
+
     Ok(ResolvedProfile {
         name: name.to_string(),
         description: def.description.clone(),
         skills,
+        versions,
     })
 }
 
-pub fn list_profiles(source_dir: &Path) -> Vec<(String, String, usize)> {
+/// Split a `group/name` profile entry, rejecting skills the library lacks.
+fn profile_skill(profile: &str, entry: &str, source_dir: &Path) -> anyhow::Result<(String, String)> {
+    let Some((group, skill_name)) = entry.split_once('/') else {
+        bail!(
+            "Profile '{}' entry '{}' must be in group/name form",
+            profile,
+            entry
+        );
+    };
+    if !source_dir.join(group).join(skill_name).join("SKILL.md").is_file() {
+        bail!("Profile '{}' references unknown skill '{}'", profile, entry);
+    }
+    Ok((group.to_string(), skill_name.to_string()))
+}
+
+pub fn list_profiles(source_dir: &Path) -> anyhow::Result<Vec<(String, String, usize)>> {
     list_profiles_inner(source_dir, true)
 }
 
 /// List profiles without builtins — for remote repos that have their own profiles.yml.
-pub fn list_profiles_remote(source_dir: &Path) -> Vec<(String, String, usize)> {
+pub fn list_profiles_remote(source_dir: &Path) -> anyhow::Result<Vec<(String, String, usize)>> {
     list_profiles_inner(source_dir, false)
 }
 
+/// Profiles the library itself defines, resolved against it. User and project
+/// profiles of this machine are left out.
+pub fn library_profiles(source_dir: &Path) -> anyhow::Result<Vec<ResolvedProfile>> {
+    let Some(defined) = load_profiles_file(source_dir)? else {
+        return Ok(Vec::new());
+    };
+    let mut profiles = builtin_profiles();
+    profiles.extend(defined.clone());
+    defined
+        .keys()
+        .map(|name| resolve_with(name, &profiles, source_dir, &mut Vec::new()))
+        .collect()
+}
+
 fn list_profiles_inner(
     source_dir: &Path,
     include_builtins: bool,
-) -> Vec<(String, String, usize)> {
-    let profiles = available_profiles_with_builtins(source_dir, include_builtins);
+) -> anyhow::Result<Vec<(String, String, usize)>> {
+    let profiles = available_profiles_with_builtins(source_dir, include_builtins)?;
     let mut result: Vec<(String, String, usize)> = profiles
-        .into_iter()
+        .iter()
         .map(|(name, def)| {
-            let count = resolve_profile(&name, source_dir)
+            let count = resolve_with(name, &profiles, source_dir, &mut Vec::new())
                 .map(|r| r.skills.len())
                 .unwrap_or(0);
-            (name, def.description, count)
+            (name.clone(), def.description.clone(), count)
         })
         .collect();
 
@@ -188,5 +369,109 @@ fn list_profiles_inner(
     ));
 
     result.sort_by(|a, b| a.0.cmp(&b.0));
-    result
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_library(root: &Path, skills: &[&str], profiles: &str) {
+        for skill in skills {
+            let dir = root.join(skill);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("SKILL.md"), "---\nname: x\n---\n").unwrap();
+        }
+        std::fs::write(root.join("profiles.yml"), profiles).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_profile_version_constraints() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_library(
+            tmp.path(),
+            &["development/git-commit-pr", "context/static-index"],
+            "",
+        );
+        std::fs::write(
+            tmp.path().join("profiles.yml"),
+            "pinned:\n  skills:\n    - development/git-commit-pr@^1.2\n    - context/static-index\n",
+        )
+        .unwrap();
+
+        let profile = resolve_profile("pinned", tmp.path()).unwrap();
+        assert_eq!(profile.skills[0], ("development".into(), "git-commit-pr".into()));
+        assert_eq!(profile.versions["development/git-commit-pr"], "^1.2");
+        assert!(!profile.versions.contains_key("context/static-index"));
+    }
+
+    #[test]
+    fn test_resolve_profile_extends_and_exclude() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_library(
+            tmp.path(),
+            &["dev/a", "dev/b", "ops/c", "ops/d"],
+            "base:\n  skills: [dev/a, dev/b@^1]\n  groups: [ops]\n\
+             team:\n  extends: [base]\n  skills: [dev/a]\n  exclude: [dev/b, ops]\n",
+        );
+
+        let team = resolve_profile("team", tmp.path()).unwrap();
+        assert_eq!(team.skills, [("dev".to_string(), "a".to_string())]);
+        assert!(team.versions.is_empty());
+        assert_eq!(resolve_profile("base", tmp.path()).unwrap().skills.len(), 4);
+    }
+
+    #[test]
+    fn test_resolve_profile_strict_errors() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_library(
+            tmp.path(),
+            &["dev/a"],
+            "x:\n  extends: [y]\ny:\n  extends: [x]\nmissing:\n  skills: [dev/zzz]\n",
+        );
+        let err = resolve_profile("x", tmp.path()).unwrap_err();
+        assert_eq!(err.to_string(), "Profile cycle: x → y → x");
+        let err = resolve_profile("missing", tmp.path()).unwrap_err();
+        assert!(err.to_string().contains("unknown skill 'dev/zzz'"));
+
+        std::fs::write(tmp.path().join("extra.yml"), "x:\n  skills: [dev/a]\n  exlude: [dev/a]\n").unwrap();
+        let err = resolve_profile("x", tmp.path()).unwrap_err();
+        assert!(format!("{:#}", err).contains("extra.yml"));
+    }
+
+    #[test]
+    fn test_unrelated_root_yaml_is_ignored() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join(".gitlab-ci.yml"), "stages: [test]\n").unwrap();
+        std::fs::write(root.join("codecov.yml"), "coverage:\n  precision: 2\n").unwrap();
+        std::fs::write(root.join("mkdocs.yml"), "site_name: Skills\nnav:\n  - Home: index.md\n")
+            .unwrap();
+        std::fs::write(root.join("team.yml"), "review:\n  skills: [dev/review]\n").unwrap();
+
+        let profiles = load_profiles_file(root).unwrap().unwrap();
+        assert_eq!(profiles.keys().collect::<Vec<_>>(), ["review"]);
+
+        std::fs::write(root.join("broken.yml"), "x:\n  skills: [a]\n  typo: 1\n").unwrap();
+        assert!(load_profiles_file(root).is_err());
+    }
+
+    #[test]
+    fn test_save_profile() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_library(tmp.path(), &["dev/a", "dev/b"], "");
+        let path = tmp.path().join("profiles.yml");
+        let def = ProfileDef {
+            description: "Mine".to_string(),
+            skills: vec!["dev/a".to_string(), "dev/b@^1".to_string()],
+            ..Default::default()
+        };
+
+        save_profile(&path, "mine", &def, false).unwrap();
+        assert!(save_profile(&path, "mine", &def, false).is_err());
+        let profile = resolve_profile("mine", tmp.path()).unwrap();
+        assert_eq!(profile.description, "Mine");
+        assert_eq!(profile.skills.len(), 2);
+        assert_eq!(profile.versions["dev/b"], "^1");
+    }
 }
@@ -0,0 +1,383 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Once, OnceLock};
+
+use super::SkillAgent;
+
+/// Effective settings: the `config_layers` merged, later layers winning.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// Default `--agent` for skill commands.
+    pub agent: Option<SkillAgent>,
+    /// `--from` for `agt skill install` and `agt persona install` requests
+    /// that no local library or registered source can satisfy.
+    pub source: Option<String>,
+    #[serde(default)]
+    pub llm: LlmSettings,
+    #[serde(default)]
+    pub paths: PathSettings,
+    #[serde(default)]
+    pub verify: VerifySettings,
+}
+
+/// `[llm]` — which CLI runs prompts and how.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LlmSettings {
+    /// Default `--llm`: claude, codex, opencode, gemini or ollama.
+    pub cli: Option<String>,
+    /// Model passed to `--model` (the model to run for ollama), only when the
+    /// CLI running is the one set as `cli`.
+    pub model: Option<String>,
+    /// Codex `--sandbox` mode; unset bypasses approvals and the sandbox.
+    pub sandbox: Option<String>,
+}
+
+/// `[paths]` — directory overrides.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathSettings {
+    /// Skills library, like `AGT_DIR`.
+    pub library: Option<PathBuf>,
+    /// Download cache, like `AGT_CACHE_DIR`.
+    pub cache: Option<PathBuf>,
+    /// Install backups, like `AGT_BACKUP_DIR`.
+    pub backups: Option<PathBuf>,
+}
+
+/// `[verify]` — provenance checks for remote skill and persona sources.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerifySettings {
+    /// Refuse sources without a SHA256SUMS manifest signed by a trusted key.
+    #[serde(default)]
+    pub require: bool,
+    /// Trusted minisign public keys (the base64 line of `minisign.pub`).
+    #[serde(default)]
+    pub trusted_keys: Vec<String>,
+}
+
+/// One source of settings, lowest precedence first in `config_layers`.
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    /// `system`, `user`, `project` or `env`
+    pub name: &'static str,
+    /// File the layer was read from; `None` for the environment
+    pub path: Option<PathBuf>,
+    pub table: toml::Table,
+}
+
+impl ConfigLayer {
+    /// `user:/home/me/.config/agt/config.toml`, or `env`.
+    pub fn origin(&self) -> String {
+        match &self.path {
+            Some(path) => format!("{}:{}", self.name, path.display()),
+            None => self.name.to_string(),
+        }
+    }
+}
+
+/// Environment variables mapped onto settings keys. Later entries win.
+const ENV_KEYS: &[(&str, &str)] = &[
+    ("AGT_AGENT", "agent"),
+    ("AGT_SOURCE", "source"),
+    ("AGT_LLM", "llm.cli"),
+    ("AGT_MODEL", "llm.model"),
+    ("AGT_CODEX_SANDBOX", "llm.sandbox"),
+    ("AGENT_SKILLS_DIR", "paths.library"),
+    ("AGT_DIR", "paths.library"),
+    ("AGT_CACHE_DIR", "paths.cache"),
+    ("AGT_BACKUP_DIR", "paths.backups"),
+];
+
+/// Keys a project's `.agents/agt.toml` may set. The file comes with the
+/// repository, so anything that changes where code is fetched from or how it
+/// runs stays in the user and system files.
+pub const PROJECT_KEYS: &[&str] = &["agent"];
+
+/// Drop keys outside `PROJECT_KEYS` from a project layer, returning them.
+fn restrict_project(table: &mut toml::Table) -> Vec<String> {
+    let ignored: Vec<String> = table
+        .keys()
+        .filter(|key| !PROJECT_KEYS.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    for key in &ignored {
+        table.remove(key);
+    }
+    ignored
+}
+
+/// `$AGT_SYSTEM_CONFIG`, else `/etc/agt/config.toml`.
+pub fn system_settings_path() -> PathBuf {
+    match std::env::var("AGT_SYSTEM_CONFIG") {
+        Ok(path) if !path.is_empty() => PathBuf::from(path),
+        _ => PathBuf::from("/etc/agt/config.toml"),
+    }
+}
+
+/// `$AGT_CONFIG`, else `~/.config/agt/config.toml` (platform config dir).
+pub fn settings_path() -> PathBuf {
+    if let Ok(path) = std::env::var("AGT_CONFIG") {
+        if !path.is_empty() {
+            return PathBuf::from(path);
+        }
+    }
+    super::config_dir().join("config.toml")
+}
+
+/// Project settings: `.agents/agt.toml` at the project root.
+pub fn project_settings_path() -> PathBuf {
+    super::project_root().join(".agents/agt.toml")
+}
+
+/// System, user and project config files, then the environment.
+/// Missing files are skipped; invalid ones are errors naming the file.
+pub fn config_layers() -> Result<Vec<ConfigLayer>> {
+    let mut layers = Vec::new();
+    for (name, path) in [
+        ("system", system_settings_path()),
+        ("user", settings_path()),
+        ("project", project_settings_path()),
+    ] {
+        if !path.is_file() {
+            continue;
+        }
+        let mut table = read_table(&path)?;
+        // A cloned repository must not redirect installs, paths or the LLM
+        // sandbox, or loosen source verification
+        let ignored = if name == "project" {
+            restrict_project(&mut table)
+        } else {
+            Vec::new()
+        };
+        if !ignored.is_empty() {
+            static WARNED: Once = Once::new();
+            WARNED.call_once(|| {
+                crate::ui::warn(&format!(
+                    "Ignoring {} in {}; only {} can be set per project",
+                    ignored.join(", "),
+                    path.display(),
+                    PROJECT_KEYS.join(", ")
+                ))
+            });
+        }
+        layers.push(ConfigLayer {
+            name,
+            path: Some(path),
+            table,
+        });
+    }
+
+    let mut env = toml::Table::new();
+    for (var, key) in ENV_KEYS {
+        if let Ok(value) = std::env::var(var) {
+            if !value.is_empty() {
+                set_key(&mut env, key, toml::Value::String(value));
+            }
+        }
+    }
+    if !env.is_empty() {
+        layers.push(ConfigLayer {
+            name: "env",
+            path: None,
+            table: env,
+        });
+    }
+    Ok(layers)
+}
+
+/// Load settings from all layers; no config files yields the defaults.
+pub fn load_settings() -> Result<Settings> {
+    let mut merged = toml::Table::new();
+    for layer in config_layers()? {
+        merge_table(&mut merged, layer.table);
+    }
+    let mut settings: Settings = toml::Value::Table(merged)
+        .try_into()
+        .context("Invalid agt settings")?;
+    for path in [
+        &mut settings.paths.library,
+        &mut settings.paths.cache,
+        &mut settings.paths.backups,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        *path = expand_home(path);
+    }
+    Ok(settings)
+}
+
+/// Default `--agent`: the configured one, else Claude.
+/// Falls back silently so a broken config never blocks argument parsing.
+pub fn default_agent() -> SkillAgent {
+    static AGENT: OnceLock<SkillAgent> = OnceLock::new();
+    *AGENT.get_or_init(|| {
+        load_settings()
+            .ok()
+            .and_then(|s| s.agent)
+            .unwrap_or_default()
+    })
+}
+
+/// The `source` setting, the fallback `--from`.
+pub fn default_source() -> Option<String> {
+    load_settings().ok()?.source
+}
+
+/// Read a config file as a TOML table, checking it against `Settings`.
+pub fn read_table(path: &Path) -> Result<toml::Table> {
+    let content =
+        fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+    toml::from_str::<Settings>(&content)
+        .context(format!("Invalid settings in {}", path.display()))?;
+    Ok(toml::from_str(&content)?)
+}
+
+/// Value at a dotted key such as `llm.model`.
+pub fn get_key<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    let (head, rest) = match key.split_once('.') {
+        Some((head, rest)) => (head, Some(rest)),
+        None => (key, None),
+    };
+    match (table.get(head)?, rest) {
+        (toml::Value::Table(inner), Some(rest)) => get_key(inner, rest),
+        (value, None) => Some(value),
+        _ => None,
+    }
+}
+
+/// Set a dotted key, creating intermediate tables.
+pub fn set_key(table: &mut toml::Table, key: &str, value: toml::Value) {
+    match key.split_once('.') {
+        Some((head, rest)) => {
+            let inner = table
+                .entry(head)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !inner.is_table() {
+                *inner = toml::Value::Table(toml::Table::new());
+            }
+            if let toml::Value::Table(inner) = inner {
+                set_key(inner, rest, value);
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Leaf values of a table as (dotted key, value), in key order.
+pub fn flatten_table(table: &toml::Table) -> Vec<(String, toml::Value)> {
+    let mut out = Vec::new();
+    for (key, value) in table {
+        match value {
+            toml::Value::Table(inner) => {
+                for (sub, value) in flatten_table(inner) {
+                    out.push((format!("{}.{}", key, sub), value));
+                }
+            }
+            _ => out.push((key.clone(), value.clone())),
+        }
+    }
+    out
+}
+
+/// Write `value` at `key` into the config file at `path`, validating the result.
+pub fn write_setting(path: &Path, key: &str, value: toml::Value) -> Result<()> {
+    let mut table = if path.is_file() {
+        toml::from_str(&fs::read_to_string(path)?)
+            .context(format!("Invalid settings in {}", path.display()))?
+    } else {
+        toml::Table::new()
+    };
+    set_key(&mut table, key, value);
+    if let Err(e) = toml::Value::Table(table.clone()).try_into::<Settings>() {
+        bail!("Cannot set '{}': {}", key, e.to_string().trim_end());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, toml::to_string(&table)?).context(format!("Failed to write {}", path.display()))
+}
+
+fn merge_table(base: &mut toml::Table, over: toml::Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(over)) => merge_table(base, over),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verify_settings() {
+        let settings: Settings = toml::from_str(
+            "[verify]\nrequire = true\ntrusted_keys = [\"RWQBAgMEBQYHCA==\"]\n",
+        )
+        .unwrap();
+        assert!(settings.verify.require);
+        assert_eq!(settings.verify.trusted_keys.len(), 1);
+
+        let empty: Settings = toml::from_str("").unwrap();
+        assert!(!empty.verify.require);
+        assert!(toml::from_str::<Settings>("[verify]\nrequried = true\n").is_err());
+    }
+
+    #[test]
+    fn test_project_layer_keeps_only_allowed_keys() {
+        let mut table: toml::Table = toml::from_str(
+            "agent = \"codex\"\nsource = \"evil/skills\"\n\
+             [llm]\nsandbox = \"danger-full-access\"\n[paths]\nlibrary = \"/tmp\"\n",
+        )
+        .unwrap();
+        assert_eq!(restrict_project(&mut table), ["llm", "paths", "source"]);
+        assert_eq!(table.keys().collect::<Vec<_>>(), ["agent"]);
+    }
+
+    #[test]
+    fn test_merge_layers_and_dotted_keys() {
+        let mut merged: toml::Table =
+            toml::from_str("agent = \"codex\"\n[llm]\ncli = \"claude\"\nmodel = \"opus\"\n").unwrap();
+        merge_table(&mut merged, toml::from_str("[llm]\nmodel = \"sonnet\"\n").unwrap());
+
+        assert_eq!(get_key(&merged, "llm.cli").and_then(|v| v.as_str()), Some("claude"));
+        assert_eq!(get_key(&merged, "llm.model").and_then(|v| v.as_str()), Some("sonnet"));
+        assert!(get_key(&merged, "llm.model.x").is_none());
+
+        set_key(&mut merged, "paths.cache", toml::Value::String("/tmp/c".into()));
+        let keys: Vec<String> = flatten_table(&merged).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, ["agent", "llm.cli", "llm.model", "paths.cache"]);
+
+        let settings: Settings = toml::Value::Table(merged).try_into().unwrap();
+        assert_eq!(settings.agent, Some(SkillAgent::Codex));
+        assert_eq!(settings.paths.cache, Some(PathBuf::from("/tmp/c")));
+    }
+
+    #[test]
+    fn test_write_setting_rejects_unknown_keys() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("agt/config.toml");
+        write_setting(&path, "llm.model", toml::Value::String("opus".into())).unwrap();
+        assert!(write_setting(&path, "llm.modle", toml::Value::String("x".into())).is_err());
+        assert!(write_setting(&path, "agent", toml::Value::String("vim".into())).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[llm]\nmodel = \"opus\"\n");
+    }
+}
@@ -0,0 +1,202 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A skills library registered with `agt source add`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillSource {
+    pub name: String,
+    /// Local library directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Remote spec, mirrored under `source_mirror_dir`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl SkillSource {
+    /// Directory holding the source's skill groups.
+    pub fn dir(&self) -> PathBuf {
+        match &self.path {
+            Some(path) => path.clone(),
+            None => source_mirror_dir().join(&self.name),
+        }
+    }
+
+    /// `path` or `url`, for display.
+    pub fn location(&self) -> String {
+        match (&self.path, &self.url) {
+            (Some(path), _) => path.display().to_string(),
+            (None, Some(url)) => url.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SourcesFile {
+    #[serde(default, rename = "source")]
+    sources: Vec<SkillSource>,
+}
+
+/// Name of the discovered library (`find_source_dir`) among the sources.
+pub const DEFAULT_SOURCE: &str = "default";
+
+/// Registered sources: `~/.config/agt/sources.toml`.
+pub fn sources_path() -> PathBuf {
+    super::config_dir().join("sources.toml")
+}
+
+/// Where remote sources are mirrored: `$XDG_DATA_HOME/agt/sources`.
+pub fn source_mirror_dir() -> PathBuf {
+    dirs::data_dir()
+        .or_else(|| dirs::home_dir().map(|h| h.join(".local/share")))
+        .unwrap_or_else(|| PathBuf::from("~/.local/share"))
+        .join("agt/sources")
+}
+
+/// Registered sources in priority order; a missing file registers none.
+pub fn load_sources() -> Result<Vec<SkillSource>> {
+    let path = sources_path();
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).context(format!("Failed to read {}", path.display()))?;
+    let file: SourcesFile =
+        toml::from_str(&content).context(format!("Invalid sources in {}", path.display()))?;
+    Ok(file.sources)
+}
+
+/// Write the registered sources, keeping their order as the priority.
+pub fn save_sources(sources: &[SkillSource]) -> Result<()> {
+    let path = sources_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = SourcesFile {
+        sources: sources.to_vec(),
+    };
+    fs::write(&path, toml::to_string(&file)?)
+        .context(format!("Failed to write {}", path.display()))
+}
+
+/// Every library to search, highest priority first: registered sources that
+/// are present on disk, then the discovered library as `default`.
+pub fn skill_sources() -> Vec<SkillSource> {
+    let mut sources: Vec<SkillSource> = match load_sources() {
+        Ok(sources) => sources,
+        Err(e) => {
+            crate::ui::warn(&format!("{:#}", e));
+            Vec::new()
+        }
+    };
+    sources.retain(|s| s.dir().is_dir());
+
+    if let Some(dir) = super::find_source_dir().or_else(super::find_cwd_source_dir) {
+        let same = |a: &Path, b: &Path| a.canonicalize().ok() == b.canonicalize().ok();
+        if !sources.iter().any(|s| same(&s.dir(), &dir)) {
+            sources.push(SkillSource {
+                name: DEFAULT_SOURCE.to_string(),
+                path: Some(dir),
+                url: None,
+            });
+        }
+    }
+    sources
+}
+
+/// Split `company:dev/deploy` into the source and the skill reference.
+pub fn split_qualified(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((source, rest)) => (Some(source), rest),
+        None => (None, name),
+    }
+}
+
+/// A skill found in one of the `skill_sources`.
+#[derive(Debug, Clone)]
+pub struct SourceSkill {
+    pub source: SkillSource,
+    pub group: String,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Find `name`, `group/name` or `source:[group/]name` in the sources, in
+/// priority order. Also returns the other sources that have the skill.
+pub fn find_source_skill(reference: &str) -> Result<Option<(SourceSkill, Vec<String>)>> {
+    let (source_name, skill_ref) = split_qualified(reference);
+    let (group, name) = match skill_ref.split_once('/') {
+        Some((group, name)) => (Some(group), name),
+        None => (None, skill_ref),
+    };
+    for part in group.into_iter().chain([name]) {
+        crate::util::validate_name(part)?;
+    }
+
+    let sources = skill_sources();
+    if let Some(source_name) = source_name {
+        if !sources.iter().any(|s| s.name == source_name) {
+            bail!("Unknown source '{}' (see: agt source list)", source_name);
+        }
+    }
+
+    let mut found: Vec<SourceSkill> = Vec::new();
+    for source in sources {
+        if source_name.is_some_and(|n| n != source.name) {
+            continue;
+        }
+        let dir = source.dir();
+        let groups = match group {
+            Some(group) => vec![group.to_string()],
+            None => super::skill_groups(&dir),
+        };
+        let hit = groups
+            .into_iter()
+            .find(|g| dir.join(g).join(name).join("SKILL.md").is_file());
+        if let Some(group) = hit {
+            found.push(SourceSkill {
+                path: dir.join(&group).join(name),
+                source,
+                group,
+                name: name.to_string(),
+            });
+        }
+    }
+
+    if found.is_empty() {
+        return Ok(None);
+    }
+    let first = found.remove(0);
+    let others = found.into_iter().map(|s| s.source.name).collect();
+    Ok(Some((first, others)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_file_keeps_order() {
+        let file: SourcesFile = toml::from_str(
+            "[[source]]\nname = \"company\"\nurl = \"github.com/acme/skills\"\n\n\
+             [[source]]\nname = \"mine\"\npath = \"/tmp/skills\"\n",
+        )
+        .unwrap();
+        let names: Vec<&str> = file.sources.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["company", "mine"]);
+        assert_eq!(file.sources[1].dir(), PathBuf::from("/tmp/skills"));
+        assert!(file.sources[0].dir().ends_with("agt/sources/company"));
+        assert!(toml::from_str::<SourcesFile>("[[source]]\nname = \"x\"\nurl = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_split_qualified() {
+        assert_eq!(split_qualified("company:dev/deploy"), (Some("company"), "dev/deploy"));
+        assert_eq!(split_qualified("deploy"), (None, "deploy"));
+    }
+}
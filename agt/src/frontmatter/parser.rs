@@ -41,6 +41,21 @@ where
     deserializer.deserialize_any(StringOrVec)
 }
 
+/// Deserializes a scalar YAML may read as a number (`version: 1.2`) into a string.
+fn scalar_string<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    match Option::<serde_yaml::Value>::deserialize(deserializer)? {
+        None | Some(serde_yaml::Value::Null) => Ok(None),
+        Some(serde_yaml::Value::String(s)) => Ok(Some(s)),
+        Some(serde_yaml::Value::Number(n)) => Ok(Some(n.to_string())),
+        Some(_) => Err(D::Error::custom("expected a version string")),
+    }
+}
+
 #[derive(Debug, Default, Deserialize)]
 pub struct Frontmatter {
     pub name: Option<String>,
@@ -53,6 +68,12 @@ pub struct Frontmatter {
     pub tags: Option<Vec<String>>,
     #[serde(rename = "trigger-keywords", default, deserialize_with = "string_or_vec")]
     pub trigger_keywords: Option<Vec<String>>,
+    /// Other skills (`group/name` or `name`) this skill needs installed
+    #[serde(default, deserialize_with = "string_or_vec")]
+    pub requires: Option<Vec<String>>,
+    /// Semver release of the skill, matched against profile constraints
+    #[serde(default, deserialize_with = "scalar_string")]
+    pub version: Option<String>,
     #[serde(rename = "allowed-tools")]
     #[allow(dead_code)]
     pub allowed_tools: Option<String>,
@@ -153,6 +174,14 @@ tags: [security, owasp]
         assert_eq!(fm.kind.as_deref(), Some("review"));
     }
 
+    #[test]
+    fn test_parse_version_as_string() {
+        let (fm, _) = parse("---\nname: a\nversion: 1.2\n---\n").unwrap();
+        assert_eq!(fm.version.as_deref(), Some("1.2"));
+        let (fm, _) = parse("---\nname: a\nversion: 1.2.3\n---\n").unwrap();
+        assert_eq!(fm.version.as_deref(), Some("1.2.3"));
+    }
+
     #[test]
     fn test_get_field() {
         let content = r#"---
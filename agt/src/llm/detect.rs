@@ -41,9 +41,24 @@ pub fn parse_cli(name: &str) -> anyhow::Result<LlmCli> {
     }
 }
 
-/// Detect available LLM CLI.
+/// The CLI set as `llm.cli` in the agt settings, if any.
+pub fn configured() -> Option<LlmCli> {
+    let name = crate::config::load_settings().ok()?.llm.cli?;
+    match parse_cli(&name) {
+        Ok(cli) => Some(cli),
+        Err(e) => {
+            crate::ui::warn(&format!("Ignoring llm.cli setting: {:#}", e));
+            None
+        }
+    }
+}
+
+/// Detect available LLM CLI, unless one is configured.
 /// Priority: codex > claude (skip if CLAUDECODE set) > opencode > gemini > ollama
 pub fn detect() -> Option<LlmCli> {
+    if let Some(cli) = configured() {
+        return Some(cli);
+    }
     if command_exists("codex") {
         return Some(LlmCli::Codex);
     }
@@ -71,6 +86,9 @@ pub fn detect() -> Option<LlmCli> {
 /// Detect LLM CLI with claude as first priority (ignores CLAUDECODE env).
 /// Used for non-interactive prompt execution where claude -p is preferred.
 pub fn detect_prefer_claude() -> Option<LlmCli> {
+    if let Some(cli) = configured() {
+        return Some(cli);
+    }
     if command_exists("claude") {
         return Some(LlmCli::Claude);
     }
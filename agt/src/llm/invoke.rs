@@ -8,17 +8,36 @@ use std::thread;
 /// Uses stdin to pass prompts to avoid OS ARG_MAX limits.
 /// Streams stdout in real-time so users can see progress.
 pub fn invoke(cli: LlmCli, prompt: &str) -> Result<String> {
+    let settings = match crate::config::load_settings() {
+        Ok(settings) => settings.llm,
+        Err(e) => {
+            crate::ui::warn(&format!("Ignoring agt settings: {:#}", e));
+            Default::default()
+        }
+    };
+    // `llm.model` names a model of the `llm.cli` CLI, so it only applies when
+    // that CLI is the one running (not a detected or `--llm` replacement)
+    let model = settings
+        .model
+        .filter(|_| settings.cli.as_deref().and_then(|c| super::parse_cli(c).ok()) == Some(cli));
+    // `--model <name>` for CLIs that take one
+    let model_args: Vec<String> = match &model {
+        Some(model) if cli != LlmCli::Ollama => vec!["--model".into(), model.clone()],
+        _ => Vec::new(),
+    };
+
     let mut child = match cli {
         LlmCli::Codex => {
             // Default to fully-bypassed approvals+sandbox — that's the only mode
             // that makes sense for an unattended `agt skill use` run. If the user
-            // sets AGT_CODEX_SANDBOX (read-only | workspace-write |
+            // sets llm.sandbox or AGT_CODEX_SANDBOX (read-only | workspace-write |
             // danger-full-access), we hand control back to codex's --sandbox flag.
             let mut args: Vec<String> = vec!["exec".into()];
-            match std::env::var("AGT_CODEX_SANDBOX") {
-                Ok(mode) => args.extend(["--sandbox".into(), mode]),
-                Err(_) => args.push("--dangerously-bypass-approvals-and-sandbox".into()),
+            match settings.sandbox {
+                Some(mode) => args.extend(["--sandbox".into(), mode]),
+                None => args.push("--dangerously-bypass-approvals-and-sandbox".into()),
             }
+            args.extend(model_args);
             args.extend(["--skip-git-repo-check".into(), "-".into()]);
             Command::new("codex")
                 .args(&args)
@@ -31,6 +50,7 @@ pub fn invoke(cli: LlmCli, prompt: &str) -> Result<String> {
 
         LlmCli::Claude => Command::new("claude")
             .args(["-p", "-", "--output-format", "text", "--dangerously-skip-permissions"])
+            .args(&model_args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -38,7 +58,9 @@ pub fn invoke(cli: LlmCli, prompt: &str) -> Result<String> {
             .context("Failed to spawn claude")?,
 
         LlmCli::OpenCode => Command::new("opencode")
-            .args(["run", "-q", "-f", "text", "-"])
+            .args(["run", "-q", "-f", "text"])
+            .args(&model_args)
+            .arg("-")
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -47,6 +69,7 @@ pub fn invoke(cli: LlmCli, prompt: &str) -> Result<String> {
 
         LlmCli::Gemini => Command::new("gemini")
             .args(["-p", "-", "-o", "text"])
+            .args(&model_args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -54,7 +77,10 @@ pub fn invoke(cli: LlmCli, prompt: &str) -> Result<String> {
             .context("Failed to spawn gemini")?,
 
         LlmCli::Ollama => {
-            let model = std::env::var("OLLAMA_MODEL").unwrap_or_else(|_| "llama3.2".to_string());
+            let model = std::env::var("OLLAMA_MODEL")
+                .ok()
+                .or(model)
+                .unwrap_or_else(|| "llama3.2".to_string());
             Command::new("ollama")
                 .args(["run", &model])
                 .stdin(Stdio::piped())
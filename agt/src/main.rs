@@ -71,6 +71,68 @@ enum Commands {
         #[command(subcommand)]
         action: cmd::persona::PersonaAction,
     },
+    /// Create, inspect and export skill profiles
+    Profile {
+        #[command(subcommand)]
+        action: cmd::profile::ProfileAction,
+    },
+    /// Build the index.json catalog a skills repository publishes
+    Index {
+        #[command(subcommand)]
+        action: cmd::index::IndexAction,
+    },
+    /// Register skill libraries searched by list, install, run and completion
+    Source {
+        #[command(subcommand)]
+        action: cmd::source::SourceAction,
+    },
+    /// Install and remove project items to match the [sync] section of agt.toml
+    ///
+    /// Undeclared skills and personas that agt installed are removed. Hooks and
+    /// teams are install-only: dropping one from [sync] leaves it in place.
+    Sync {
+        /// Only report drift; exit non-zero if anything would change
+        /// (undeclared hooks and teams are not drift)
+        #[arg(long)]
+        check: bool,
+    },
+    /// Check installed skills, personas, hooks and settings for problems
+    Doctor {
+        /// Repair what is safe to repair (dangling symlinks, hooks with missing scripts)
+        #[arg(long)]
+        fix: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Read and write agt settings (system, user, project, environment)
+    Config {
+        #[command(subcommand)]
+        action: cmd::config::ConfigAction,
+    },
+    /// Manage the download cache of remote repository tarballs
+    Cache {
+        #[command(subcommand)]
+        action: cmd::cache::CacheAction,
+    },
+    /// Search skills and personas in every source and install directory
+    Search {
+        /// Words to look for in names, descriptions, tags and trigger keywords
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Only search skills
+        #[arg(long, conflicts_with = "personas")]
+        skills: bool,
+        /// Only search personas
+        #[arg(long)]
+        personas: bool,
+        /// Maximum number of results
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Run prompt with skill matching
     Run {
         /// The prompt to execute
@@ -105,6 +167,20 @@ fn main() {
         Commands::Hook { action } => cmd::hook::execute(action),
         Commands::Team { action } => cmd::team::execute(action),
         Commands::Persona { action } => cmd::persona::execute(action),
+        Commands::Profile { action } => cmd::profile::execute(action),
+        Commands::Index { action } => cmd::index::execute(action),
+        Commands::Source { action } => cmd::source::execute(action),
+        Commands::Sync { check } => cmd::sync::execute(check),
+        Commands::Doctor { fix, json } => cmd::doctor::execute(fix, json),
+        Commands::Config { action } => cmd::config::execute(action),
+        Commands::Cache { action } => cmd::cache::execute(action),
+        Commands::Search {
+            query,
+            skills,
+            personas,
+            limit,
+            json,
+        } => cmd::search::execute(&query, skills, personas, limit, json),
         Commands::Run { prompt, skill, llm } => {
             cmd::run::execute(&prompt.join(" "), skill.as_deref(), llm.as_deref())
         }
@@ -144,6 +220,15 @@ fn generate_completions(shell: Shell) {
     }
 }
 
+/// Space-separated `--agent` values for completion scripts.
+fn agent_names() -> String {
+    <config::SkillAgent as clap::ValueEnum>::value_variants()
+        .iter()
+        .map(|agent| agent.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn print_zsh_completions(base: &str) {
     // Print base completions from clap
     print!("{}", base);
@@ -181,7 +266,7 @@ _agt_persona_install() {{
         '1:persona name:_agt_persona_names' \
         '-g[Install globally]' \
         '--global[Install globally]' \
-        '--agent=[Target agent]:agent:(claude codex)' \
+        '--agent=[Target agent]:agent:({agents})' \
         '-f[Force overwrite]' \
         '--force[Force overwrite]' \
         '-a[Install all]' \
@@ -263,6 +348,7 @@ _agt_skill_install() {{
         '--profile=[Install profile]:profile:(core dev agents integrations ml full all)' \
         '-a[Install all skills]' \
         '--all[Install all skills]' \
+        '--agent=[Target agent]:agent:({agents})' \
         '--from=[Remote spec]:spec:'
 }}
 
@@ -271,13 +357,13 @@ _agt_skill_uninstall() {{
         '1:skill name:_agt_skill_names' \
         '-g[Global scope]' \
         '--global[Global scope]' \
-        '--agent=[Target agent]:agent:(claude codex)'
+        '--agent=[Target agent]:agent:({agents})'
 }}
 
 _agt_skill_which() {{
     _arguments \
         '1:skill name:_agt_skill_names' \
-        '--agent=[Target agent]:agent:(claude codex)'
+        '--agent=[Target agent]:agent:({agents})'
 }}
 
 _agt_skill_update() {{
@@ -287,9 +373,9 @@ _agt_skill_update() {{
         '--global[Global only]' \
         '-l[Local only]' \
         '--local[Local only]' \
-        '--agent=[Target agent]:agent:(claude codex)'
+        '--agent=[Target agent]:agent:({agents})'
 }}
-"#);
+"#, agents = agent_names());
 }
 
 fn print_bash_completions(base: &str) {
@@ -448,10 +534,12 @@ fn complete_names(kind: &str) {
             // Global
             collect_names_from_dir(&config::global_skill_target(), &mut names);
             collect_names_from_dir(&config::global_codex_skill_target(), &mut names);
-            // Library
-            if let Some(source_dir) = config::find_source_dir() {
+            // Every source, plus qualified names for picking one
+            for source in config::skill_sources() {
+                let source_dir = source.dir();
                 for group in config::skill_groups(&source_dir) {
                     for skill in config::skills_in_group(&source_dir, &group) {
+                        names.insert(format!("{}:{}/{}", source.name, group, skill));
                         names.insert(skill);
                     }
                 }
@@ -0,0 +1,234 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::RemoteSpec;
+use crate::config;
+
+/// A cached repository tarball.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    pub commit: String,
+    pub size: u64,
+    pub path: PathBuf,
+}
+
+fn tarball_root() -> PathBuf {
+    config::cache_dir().join("tarballs")
+}
+
+/// Cache location for a pinned spec. Unpinned specs are never cached —
+/// a branch name says nothing about the content behind it.
+fn tarball_path(root: &Path, spec: &RemoteSpec) -> Option<PathBuf> {
+    let commit = spec.commit.as_deref()?;
+    Some(
+        root.join(spec.host())
+            .join(&spec.owner)
+            .join(&spec.repo)
+            .join(format!("{}.tar.gz", commit)),
+    )
+}
+
+/// Cached tarball for the spec's pinned commit, if present.
+pub fn lookup(spec: &RemoteSpec) -> Option<PathBuf> {
+    tarball_path(&tarball_root(), spec).filter(|p| p.is_file())
+}
+
+/// Store a downloaded tarball for the spec's pinned commit.
+pub fn store(spec: &RemoteSpec, tarball: &[u8]) -> Result<()> {
+    store_in(&tarball_root(), spec, tarball)
+}
+
+fn store_in(root: &Path, spec: &RemoteSpec, tarball: &[u8]) -> Result<()> {
+    let Some(dest) = tarball_path(root, spec) else {
+        return Ok(());
+    };
+    let dir = dest.parent().context("Invalid cache path")?;
+    fs::create_dir_all(dir).context(format!("Failed to create {}", dir.display()))?;
+
+    // Write beside the final path and rename, so readers never see a partial file
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)
+        .context(format!("Failed to create temp file in {}", dir.display()))?;
+    tmp.write_all(tarball).context("Failed to write cached tarball")?;
+    tmp.persist(&dest)
+        .context(format!("Failed to write {}", dest.display()))?;
+    Ok(())
+}
+
+/// All cached tarballs, sorted by host/owner/repo/commit.
+pub fn list_cache() -> Vec<CacheEntry> {
+    list_in(&tarball_root())
+}
+
+fn list_in(root: &Path) -> Vec<CacheEntry> {
+    let mut entries = Vec::new();
+    for host in sorted_dirs(root) {
+        for owner in sorted_dirs(&host) {
+            for repo in sorted_dirs(&owner) {
+                let Ok(files) = fs::read_dir(&repo) else {
+                    continue;
+                };
+                for file in files.flatten() {
+                    let path = file.path();
+                    let Some(commit) = path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .and_then(|n| n.strip_suffix(".tar.gz"))
+                    else {
+                        continue;
+                    };
+                    entries.push(CacheEntry {
+                        host: file_name(&host),
+                        owner: file_name(&owner),
+                        repo: file_name(&repo),
+                        commit: commit.to_string(),
+                        size: file.metadata().map(|m| m.len()).unwrap_or(0),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+    }
+    entries.sort_by(|a, b| {
+        (&a.host, &a.owner, &a.repo, &a.commit).cmp(&(&b.host, &b.owner, &b.repo, &b.commit))
+    });
+    entries
+}
+
+impl CacheEntry {
+    /// `owner/repo`, prefixed with the host unless it is github.com.
+    pub fn label(&self) -> String {
+        if self.host == super::DEFAULT_HOST {
+            format!("{}/{}", self.owner, self.repo)
+        } else {
+            format!("{}/{}/{}", self.host, self.owner, self.repo)
+        }
+    }
+
+    /// Whether `filter` names this entry's host, owner or repository,
+    /// e.g. `jiunbae`, `jiunbae/agent-skills` or `gitea.example.com/org`.
+    fn matches(&self, filter: &str) -> bool {
+        let full = format!("{}/{}/{}", self.host, self.owner, self.repo);
+        [self.label(), full].iter().any(|name| {
+            name == filter
+                || name
+                    .strip_prefix(filter)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Remove cached tarballs, optionally only those matching `[host/]owner[/repo]`.
+/// Returns the removed entries.
+pub fn clean_cache(filter: Option<&str>) -> Result<Vec<CacheEntry>> {
+    clean_in(&tarball_root(), filter)
+}
+
+fn clean_in(root: &Path, filter: Option<&str>) -> Result<Vec<CacheEntry>> {
+    let filter = filter.map(|f| f.trim_matches('/'));
+    let removed: Vec<CacheEntry> = list_in(root)
+        .into_iter()
+        .filter(|e| filter.is_none_or(|f| e.matches(f)))
+        .collect();
+
+    for entry in &removed {
+        fs::remove_file(&entry.path)
+            .context(format!("Failed to remove {}", entry.path.display()))?;
+    }
+
+    // Drop now-empty host/owner/repo directories
+    for host_dir in sorted_dirs(root) {
+        for owner_dir in sorted_dirs(&host_dir) {
+            for repo_dir in sorted_dirs(&owner_dir) {
+                let _ = fs::remove_dir(&repo_dir);
+            }
+            let _ = fs::remove_dir(&owner_dir);
+        }
+        let _ = fs::remove_dir(&host_dir);
+    }
+
+    Ok(removed)
+}
+
+fn sorted_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pinned(owner: &str, repo: &str, commit: &str) -> RemoteSpec {
+        RemoteSpec {
+            host: "github.com".into(),
+            owner: owner.into(),
+            repo: repo.into(),
+            git_ref: "main".into(),
+            commit: Some(commit.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unpinned_spec_is_not_cached() {
+        let tmp = tempfile::tempdir().unwrap();
+        let spec = RemoteSpec {
+            owner: "o".into(),
+            repo: "r".into(),
+            git_ref: "main".into(),
+            ..Default::default()
+        };
+        store_in(tmp.path(), &spec, b"data").unwrap();
+        assert!(tarball_path(tmp.path(), &spec).is_none());
+        assert!(list_in(tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn test_store_list_and_clean() {
+        let tmp = tempfile::tempdir().unwrap();
+        store_in(tmp.path(), &pinned("a", "one", "c1"), b"12345").unwrap();
+        store_in(tmp.path(), &pinned("a", "two", "c2"), b"1").unwrap();
+        store_in(tmp.path(), &pinned("b", "one", "c3"), b"1").unwrap();
+        let mut gitea = pinned("a", "one", "c4");
+        gitea.host = "git.example.com".into();
+        store_in(tmp.path(), &gitea, b"1").unwrap();
+
+        let entries = list_in(tmp.path());
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].label(), "git.example.com/a/one");
+        assert_eq!(entries[1].commit, "c1");
+        assert_eq!(entries[1].size, 5);
+
+        let removed = clean_in(tmp.path(), Some("git.example.com")).unwrap();
+        assert_eq!(removed.len(), 1);
+
+        let removed = clean_in(tmp.path(), Some("a/one")).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(!tmp.path().join("github.com/a/one").exists());
+
+        let removed = clean_in(tmp.path(), Some("a")).unwrap();
+        assert_eq!(removed.len(), 1);
+
+        let removed = clean_in(tmp.path(), None).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(list_in(tmp.path()).is_empty());
+    }
+}
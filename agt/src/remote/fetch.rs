@@ -0,0 +1,877 @@
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tar::Archive;
+use tempfile::TempDir;
+
+use super::local::{local_root, parse_local_spec};
+use super::provider::DEFAULT_HOST;
+use super::{git, is_git_spec, is_local_spec, is_version_req, provider_for, Provider};
+
+/// Where a spec's files come from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Transport {
+    /// Repository tarballs from the host's HTTP API.
+    #[default]
+    Archive,
+    /// A directory on disk.
+    Local(PathBuf),
+    /// A `.tar.gz` bundle on disk.
+    Tarball(PathBuf),
+    /// Sparse, partial clone of a git URL with the system `git`.
+    Git(String),
+}
+
+impl Transport {
+    /// Name recorded in `.remote-source`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transport::Archive => "archive",
+            Transport::Local(_) => "local",
+            Transport::Tarball(_) => "tarball",
+            Transport::Git(_) => "git",
+        }
+    }
+
+    /// Local directory or bundle path, for on-disk transports.
+    pub fn local_path(&self) -> Option<&Path> {
+        match self {
+            Transport::Local(path) | Transport::Tarball(path) => Some(path),
+            Transport::Archive | Transport::Git(_) => None,
+        }
+    }
+}
+
+/// Parsed remote specification
+#[derive(Debug, Clone, Default)]
+pub struct RemoteSpec {
+    /// Git host, e.g. `github.com` or `gitea.example.com`. Empty means github.com.
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    pub path: String,
+    pub git_ref: String,
+    /// Exact commit `git_ref` resolved to. When set, downloads use it instead of `git_ref`.
+    pub commit: Option<String>,
+    /// Semver constraint (`^1.2`) that `git_ref` is re-resolved against on
+    /// every pin, so updates stay within it.
+    pub version_req: Option<String>,
+    pub transport: Transport,
+}
+
+impl RemoteSpec {
+    /// Same repository and ref, pointing at a different path inside it.
+    pub fn with_path(&self, path: impl Into<String>) -> RemoteSpec {
+        RemoteSpec {
+            path: path.into(),
+            ..self.clone()
+        }
+    }
+
+    /// The ref actually downloaded: the pinned commit if known, else `git_ref`.
+    pub fn download_ref(&self) -> &str {
+        self.commit.as_deref().unwrap_or(&self.git_ref)
+    }
+
+    /// Host the spec points at, defaulting to github.com.
+    pub fn host(&self) -> &str {
+        if self.host.is_empty() {
+            DEFAULT_HOST
+        } else {
+            &self.host
+        }
+    }
+
+    /// The hosting provider serving this spec.
+    pub fn provider(&self) -> Box<dyn Provider> {
+        provider_for(self.host())
+    }
+
+    /// `owner/repo`, prefixed with the host unless it is github.com.
+    /// Local sources are labelled by their directory or bundle path.
+    pub fn repo_label(&self) -> String {
+        if let Some(path) = self.transport.local_path() {
+            path.display().to_string()
+        } else if let Transport::Git(url) = &self.transport {
+            url.clone()
+        } else if self.host() == DEFAULT_HOST {
+            format!("{}/{}", self.owner, self.repo)
+        } else {
+            format!("{}/{}/{}", self.host(), self.owner, self.repo)
+        }
+    }
+}
+
+impl std::fmt::Display for RemoteSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Transport::Git(url) = &self.transport {
+            return write!(f, "{}", git::display(url, self));
+        }
+        match self.transport.local_path() {
+            Some(path) if self.path.is_empty() => write!(f, "{}", path.display()),
+            Some(path) => write!(f, "{}#{}", path.display(), self.path),
+            None => write!(f, "{}/{}@{}", self.repo_label(), self.path, self.git_ref),
+        }
+    }
+}
+
+/// Parse "[host/]owner/repo/path[@ref]" into a RemoteSpec.
+/// Also accepts URL-style input: github.com/owner/repo/path, https://gitea.example.com/owner/repo/path
+/// A leading segment containing a dot or port is taken as the host; otherwise github.com.
+/// Local directories, `file://` URLs and `.tar.gz` bundles are accepted too,
+/// as are git URLs (`git@host:org/repo.git`, `ssh://`, `git+https://`).
+/// A `@ref` like `^1.2` is a version constraint resolved against tags by `pin`.
+pub fn parse_spec(spec: &str) -> Result<RemoteSpec> {
+    let spec = spec.trim();
+    if is_local_spec(spec) && !is_git_spec(spec) {
+        return parse_local_spec(spec);
+    }
+    let mut spec = if is_git_spec(spec) {
+        git::parse_git_spec(spec)?
+    } else {
+        parse_repo_spec(spec)?
+    };
+    if is_version_req(&spec.git_ref) {
+        spec.version_req = Some(spec.git_ref.clone());
+    }
+    Ok(spec)
+}
+
+/// Parse the `[host/]owner/repo/path[@ref]` form.
+fn parse_repo_spec(spec: &str) -> Result<RemoteSpec> {
+    // Strip common URL prefixes
+    let spec_str = spec;
+    let spec = spec
+        .strip_prefix("https://")
+        .or_else(|| spec.strip_prefix("http://"))
+        .unwrap_or(spec);
+    let spec = spec.trim_end_matches('/');
+
+    // Extract @ref suffix
+    let (path_part, git_ref) = if let Some(at_pos) = spec.rfind('@') {
+        (&spec[..at_pos], spec[at_pos + 1..].to_string())
+    } else {
+        (spec, "main".to_string())
+    };
+
+    let mut parts: Vec<&str> = path_part.split('/').collect();
+    let host = match parts.first() {
+        Some(first) if parts.len() > 1 && (first.contains('.') || first.contains(':')) => {
+            parts.remove(0).to_lowercase()
+        }
+        _ => DEFAULT_HOST.to_string(),
+    };
+    let host = host.strip_prefix("www.").unwrap_or(&host).to_string();
+
+    if parts.len() < 2 || parts[0].is_empty() || parts[1].is_empty() {
+        bail!(
+            "Invalid format: {}\nExpected: [host/]owner/repo[/path/to/skill][@ref]\n\
+             Examples:\n  jiunbae/agent-skills/agents/background-reviewer\n  \
+             jiunbae/agent-skills  (browse all skills in repo)\n  \
+             gitea.example.com/org/skills@v1  (self-hosted Gitea or GitLab)",
+            spec
+        );
+    }
+
+    let spec = RemoteSpec {
+        host,
+        owner: parts[0].to_string(),
+        repo: parts[1].to_string(),
+        path: if parts.len() > 2 {
+            parts[2..].join("/")
+        } else {
+            String::new()
+        },
+        git_ref,
+        transport: Transport::Archive,
+        ..Default::default()
+    };
+    check_components(&spec).context(format!("Invalid remote spec: {}", spec_str))?;
+    Ok(spec)
+}
+
+/// Host, owner and repo name cache directories and the path is joined onto
+/// the download, so none of them may step outside with `.` or `..`.
+fn check_components(spec: &RemoteSpec) -> Result<()> {
+    crate::util::validate_name(spec.host())?;
+    crate::util::validate_name(&spec.owner)?;
+    crate::util::validate_name(&spec.repo)?;
+    if let Some(part) = spec.path.split('/').find(|p| *p == "." || *p == "..") {
+        bail!("Path cannot contain '{}': {}", part, spec.path);
+    }
+    Ok(())
+}
+
+/// Commits already resolved in this process, keyed by `owner/repo@ref`.
+/// Updating many skills from one repo then costs a single lookup.
+fn resolved_commits() -> &'static Mutex<HashMap<String, String>> {
+    static RESOLVED: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    RESOLVED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Resolve the spec's ref (branch, tag or SHA) to the exact commit SHA upstream.
+pub fn resolve_commit(spec: &RemoteSpec) -> Result<String> {
+    if spec.transport.local_path().is_some() {
+        bail!("{} is a local source and has no upstream commit", spec.repo_label());
+    }
+    if crate::util::is_commit_sha(&spec.git_ref) {
+        return Ok(spec.git_ref.to_lowercase());
+    }
+
+    let key = format!("{}@{}", spec.repo_label(), spec.git_ref);
+    if let Some(sha) = resolved_commits().lock().ok().and_then(|m| m.get(&key).cloned()) {
+        return Ok(sha);
+    }
+
+    let sha = match &spec.transport {
+        Transport::Git(url) => git::resolve_commit(url, &spec.git_ref)?,
+        _ => resolve_api_commit(spec)?,
+    };
+    if let Ok(mut m) = resolved_commits().lock() {
+        m.insert(key, sha.clone());
+    }
+    Ok(sha)
+}
+
+/// Resolve a ref through the provider's commit API.
+fn resolve_api_commit(spec: &RemoteSpec) -> Result<String> {
+    let provider = spec.provider();
+    let (url, accept) = provider.commit_request(spec);
+    let response = provider
+        .get(&url)
+        .set("Accept", accept)
+        .call()
+        .context(format!(
+            "Failed to resolve {}@{} via the {} API",
+            spec.repo_label(),
+            spec.git_ref,
+            provider.name()
+        ))?;
+
+    let body = response
+        .into_string()
+        .context("Failed to read commit response")?;
+    let sha = provider
+        .parse_commit(&body)
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    if !crate::util::is_commit_sha(&sha) {
+        bail!(
+            "Unexpected commit SHA for {}@{}: {}",
+            spec.repo_label(),
+            spec.git_ref,
+            sha
+        );
+    }
+    Ok(sha)
+}
+
+/// Pin a spec to the commit its ref currently points at, first resolving a
+/// version constraint to the highest matching tag.
+/// Resolution failures (rate limits, offline mirrors) leave the spec unpinned.
+pub fn pin(mut spec: RemoteSpec) -> RemoteSpec {
+    if spec.commit.is_none() && spec.version_req.is_some() {
+        if let Err(e) = super::resolve_version(&mut spec) {
+            crate::ui::warn(&format!("Could not resolve {}: {:#}", spec, e));
+            return spec;
+        }
+    }
+    if spec.commit.is_none() && spec.transport.local_path().is_none() {
+        match resolve_commit(&spec) {
+            Ok(sha) => spec.commit = Some(sha),
+            Err(e) => crate::ui::warn(&format!("Could not pin {} to a commit: {:#}", spec, e)),
+        }
+    }
+    spec
+}
+
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10 MB
+
+/// Download a single file through the provider's raw file endpoint
+pub fn fetch_file(spec: &RemoteSpec) -> Result<Vec<u8>> {
+    match &spec.transport {
+        Transport::Local(_) | Transport::Tarball(_) => {
+            let (_tmp_dir, root) = local_root(spec)?;
+            let file = root.join(&spec.path);
+            return fs::read(&file).context(format!("Failed to read {}", file.display()));
+        }
+        Transport::Git(url) => {
+            let (_tmp_dir, file) = git::fetch_dir(url, spec)?;
+            return fs::read(&file).context(format!("Failed to read {}", spec.path));
+        }
+        Transport::Archive => {}
+    }
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message(format!("Fetching {}...", spec.path));
+    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+
+    let provider = spec.provider();
+    let url = provider.raw_url(spec, spec.download_ref());
+
+    let response = provider
+        .get(&url)
+        .call()
+        .context(format!("Failed to download: {}", url))?;
+
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_FILE_SIZE)
+        .read_to_end(&mut body)
+        .context("Failed to read response")?;
+
+    spinner.finish_and_clear();
+    Ok(body)
+}
+
+/// Download a directory from the repository tarball, extract to a temp directory.
+/// Returns (TempDir, path_to_extracted_content).
+/// The TempDir must be kept alive by the caller — dropping it cleans up.
+///
+/// Pinned specs are served from (and stored into) the tarball cache, so
+/// installing or updating many skills from one commit downloads it once.
+pub fn fetch_dir(spec: &RemoteSpec) -> Result<(TempDir, PathBuf)> {
+    if spec.commit.is_none() && is_version_req(&spec.git_ref) {
+        bail!("{} was not resolved to a tag", spec);
+    }
+    if let Transport::Git(url) = &spec.transport {
+        return git::fetch_dir(url, spec);
+    }
+    if spec.transport != Transport::Archive {
+        let (tmp_dir, root) = local_root(spec)?;
+        let target_path = root.join(&spec.path);
+        if !target_path.exists() {
+            bail!("Path not found: {} in {}", spec.path, spec.repo_label());
+        }
+        return Ok((tmp_dir, target_path));
+    }
+
+    let tmp_dir = TempDir::new().context("Failed to create temp directory")?;
+
+    let mut extracted_root: Option<PathBuf> = None;
+
+    if let Some(cached) = super::cache::lookup(spec) {
+        if let Ok(file) = fs::File::open(&cached) {
+            extracted_root = unpack_tarball(file, tmp_dir.path());
+        }
+        if extracted_root.is_none() {
+            // Corrupt entry — drop it and fall through to a fresh download
+            let _ = fs::remove_file(&cached);
+        }
+    }
+
+    if extracted_root.is_none() {
+        extracted_root = download_tarball(spec, tmp_dir.path());
+    }
+
+    let root = extracted_root.context(format!(
+        "Download failed: {}@{}\n{}",
+        spec.repo_label(),
+        spec.git_ref,
+        spec.provider().auth_hint()
+    ))?;
+
+    let target_path = if spec.path.is_empty() {
+        root.clone()
+    } else {
+        let p = root.join(&spec.path);
+        if !p.exists() {
+            bail!(
+                "Path not found: {} in {}@{}",
+                spec.path,
+                spec.repo_label(),
+                spec.git_ref
+            );
+        }
+        p
+    };
+
+    Ok((tmp_dir, target_path))
+}
+
+/// Try each tarball URL until one downloads and extracts into `dest`.
+/// Returns the extracted root directory.
+fn download_tarball(spec: &RemoteSpec, dest: &Path) -> Option<PathBuf> {
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message(format!("Downloading {}@{}...", spec.repo_label(), spec.git_ref));
+    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+
+    let provider = spec.provider();
+    let urls = provider.archive_urls(spec, spec.download_ref(), spec.commit.is_some());
+
+    let mut extracted_root = None;
+
+    for url in &urls {
+        // Clean previous attempt
+        if let Ok(entries) = fs::read_dir(dest) {
+            for entry in entries.flatten() {
+                let _ = fs::remove_dir_all(entry.path());
+            }
+        }
+
+        let response = match provider.get(url).call() {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+
+        const MAX_TARBALL_SIZE: u64 = 50 * 1024 * 1024; // 50 MB
+        let mut body = Vec::new();
+        if response
+            .into_reader()
+            .take(MAX_TARBALL_SIZE)
+            .read_to_end(&mut body)
+            .is_err()
+        {
+            continue;
+        }
+
+        extracted_root = unpack_tarball(body.as_slice(), dest);
+        if extracted_root.is_some() {
+            if let Err(e) = super::cache::store(spec, &body) {
+                crate::ui::warn(&format!("Could not cache {}: {:#}", spec, e));
+            }
+            break;
+        }
+    }
+
+    spinner.finish_and_clear();
+    extracted_root
+}
+
+/// Extract a gzipped tarball into `dest` and return its single root directory.
+fn unpack_tarball(reader: impl Read, dest: &Path) -> Option<PathBuf> {
+    let mut archive = Archive::new(GzDecoder::new(reader));
+    archive.unpack(dest).ok()?;
+
+    fs::read_dir(dest)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.is_dir())
+}
+
+/// Write .remote-source metadata file
+pub fn write_metadata(target: &Path, spec: &RemoteSpec) -> Result<()> {
+    let metadata_path = if target.is_dir() {
+        target.join(".remote-source")
+    } else {
+        let stem = target
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        target.with_file_name(format!("{}.remote-source", stem))
+    };
+
+    let mut content = format!("transport: {}\n", spec.transport.name());
+    if let Transport::Git(url) = &spec.transport {
+        content.push_str(&format!(
+            "url: {}\npath: {}\nref: {}\n",
+            url, spec.path, spec.git_ref
+        ));
+    } else if let Some(path) = spec.transport.local_path() {
+        content.push_str(&format!(
+            "source: {}\npath: {}\n",
+            path.display(),
+            spec.path
+        ));
+    } else {
+        if spec.host() != DEFAULT_HOST {
+            content.push_str(&format!("host: {}\n", spec.host()));
+        }
+        content.push_str(&format!(
+            "source: {}/{}/{}\nref: {}\n",
+            spec.owner, spec.repo, spec.path, spec.git_ref
+        ));
+    }
+    if let Some(commit) = &spec.commit {
+        content.push_str(&format!("commit: {}\n", commit));
+    }
+    if let Some(req) = &spec.version_req {
+        content.push_str(&format!("version: {}\n", req));
+    }
+    content.push_str(&format!("installed: {}\n", chrono_like_now()));
+
+    fs::write(&metadata_path, content).context("Failed to write remote metadata")?;
+    Ok(())
+}
+
+/// Parse .remote-source metadata file back into a RemoteSpec.
+pub fn parse_metadata(skill_dir: &Path) -> Result<RemoteSpec> {
+    let metadata_path = skill_dir.join(".remote-source");
+    let content = fs::read_to_string(&metadata_path)
+        .context(format!("Failed to read {}", metadata_path.display()))?;
+
+    let mut transport = String::from("archive");
+    let mut local_path = String::new();
+    let mut url = String::new();
+    let mut host = DEFAULT_HOST.to_string();
+    let mut source = String::new();
+    let mut git_ref = "main".to_string();
+    let mut commit = None;
+    let mut version_req = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(val) = line.strip_prefix("transport:") {
+            transport = val.trim().to_string();
+        } else if let Some(val) = line.strip_prefix("url:") {
+            url = val.trim().to_string();
+        } else if let Some(val) = line.strip_prefix("path:") {
+            local_path = val.trim().to_string();
+        } else if let Some(val) = line.strip_prefix("host:") {
+            host = val.trim().to_string();
+        } else if let Some(val) = line.strip_prefix("source:") {
+            source = val.trim().to_string();
+        } else if let Some(val) = line.strip_prefix("ref:") {
+            git_ref = val.trim().to_string();
+        } else if let Some(val) = line.strip_prefix("commit:") {
+            let val = val.trim();
+            if !val.is_empty() && !crate::util::is_commit_sha(val) {
+                bail!(
+                    "Invalid .remote-source: commit '{}' is not a SHA in {}",
+                    val,
+                    metadata_path.display()
+                );
+            }
+            commit = Some(val.to_string()).filter(|c| !c.is_empty());
+        } else if let Some(val) = line.strip_prefix("version:") {
+            version_req = Some(val.trim().to_string()).filter(|v| !v.is_empty());
+        }
+    }
+
+    if transport == "git" {
+        if url.is_empty() {
+            bail!(
+                "Invalid .remote-source: missing 'url' field in {}",
+                metadata_path.display()
+            );
+        }
+        return Ok(RemoteSpec {
+            path: local_path,
+            git_ref,
+            commit,
+            version_req,
+            transport: Transport::Git(url),
+            ..Default::default()
+        });
+    }
+
+    if source.is_empty() {
+        bail!(
+            "Invalid .remote-source: missing 'source' field in {}",
+            metadata_path.display()
+        );
+    }
+
+    // Local sources record the directory or bundle in `source` and the item in `path`
+    let local_transport = match transport.as_str() {
+        "local" => Some(Transport::Local(PathBuf::from(&source))),
+        "tarball" => Some(Transport::Tarball(PathBuf::from(&source))),
+        _ => None,
+    };
+    if let Some(transport) = local_transport {
+        return Ok(RemoteSpec {
+            path: local_path,
+            transport,
+            ..Default::default()
+        });
+    }
+
+    // source is "owner/repo/path" — split into parts
+    let parts: Vec<&str> = source.splitn(3, '/').collect();
+    if parts.len() < 2 {
+        bail!("Invalid source format in .remote-source: {}", source);
+    }
+
+    let spec = RemoteSpec {
+        host,
+        owner: parts[0].to_string(),
+        repo: parts[1].to_string(),
+        path: if parts.len() > 2 {
+            parts[2].to_string()
+        } else {
+            String::new()
+        },
+        git_ref,
+        commit,
+        version_req,
+        transport: Transport::Archive,
+    };
+    check_components(&spec).context(format!("Invalid .remote-source in {}", skill_dir.display()))?;
+    Ok(spec)
+}
+
+/// Abbreviated commit SHA for display; also used on refs, so it slices by
+/// characters rather than bytes.
+pub fn short_commit(commit: &str) -> &str {
+    match commit.char_indices().nth(7) {
+        Some((end, _)) => &commit[..end],
+        None => commit,
+    }
+}
+
+fn chrono_like_now() -> String {
+    use std::time::SystemTime;
+    let duration = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = duration.as_secs();
+    // Simple UTC timestamp without chrono dependency
+    let days = secs / 86400;
+    let time_secs = secs % 86400;
+    let hours = time_secs / 3600;
+    let mins = (time_secs % 3600) / 60;
+    let s = time_secs % 60;
+
+    // Approximate date calculation (good enough for metadata)
+    let mut y = 1970i64;
+    let mut remaining_days = days as i64;
+    loop {
+        let days_in_year = if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) {
+            366
+        } else {
+            365
+        };
+        if remaining_days < days_in_year {
+            break;
+        }
+        remaining_days -= days_in_year;
+        y += 1;
+    }
+    let is_leap = y % 4 == 0 && (y % 100 != 0 || y % 400 == 0);
+    let month_days = [
+        31,
+        if is_leap { 29 } else { 28 },
+        31,
+        30,
+        31,
+        30,
+        31,
+        31,
+        30,
+        31,
+        30,
+        31,
+    ];
+    let mut m = 0;
+    for (i, &md) in month_days.iter().enumerate() {
+        if remaining_days < md as i64 {
+            m = i + 1;
+            break;
+        }
+        remaining_days -= md as i64;
+    }
+    if m == 0 {
+        m = 12;
+        remaining_days = 0;
+    }
+    let d = remaining_days + 1;
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        y, m, d, hours, mins, s
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec_basic() {
+        let spec = parse_spec("jiunbae/agent-skills/agents/background-reviewer").unwrap();
+        assert_eq!(spec.owner, "jiunbae");
+        assert_eq!(spec.repo, "agent-skills");
+        assert_eq!(spec.path, "agents/background-reviewer");
+        assert_eq!(spec.git_ref, "main");
+    }
+
+    #[test]
+    fn test_parse_spec_with_ref() {
+        let spec =
+            parse_spec("jiunbae/agent-skills/agents/background-reviewer@v2026.02.19.1").unwrap();
+        assert_eq!(spec.path, "agents/background-reviewer");
+        assert_eq!(spec.git_ref, "v2026.02.19.1");
+    }
+
+    #[test]
+    fn test_parse_spec_persona() {
+        let spec = parse_spec("jiunbae/agent-skills/personas/security-reviewer").unwrap();
+        assert_eq!(spec.path, "personas/security-reviewer");
+    }
+
+    #[test]
+    fn test_parse_spec_url_prefix() {
+        let spec = parse_spec("https://github.com/jiunbae/agent-skills/agents/background-reviewer").unwrap();
+        assert_eq!(spec.owner, "jiunbae");
+        assert_eq!(spec.repo, "agent-skills");
+        assert_eq!(spec.path, "agents/background-reviewer");
+
+        let spec = parse_spec("github.com/jiunbae/agent-skills/context/context-manager").unwrap();
+        assert_eq!(spec.owner, "jiunbae");
+        assert_eq!(spec.path, "context/context-manager");
+    }
+
+    #[test]
+    fn test_parse_spec_repo_only() {
+        let spec = parse_spec("jiunbae/agent-skills").unwrap();
+        assert_eq!(spec.owner, "jiunbae");
+        assert_eq!(spec.repo, "agent-skills");
+        assert_eq!(spec.path, "");
+        assert_eq!(spec.git_ref, "main");
+
+        let spec = parse_spec("github.com/jiunbae/agent-skills").unwrap();
+        assert_eq!(spec.owner, "jiunbae");
+        assert_eq!(spec.path, "");
+    }
+
+    #[test]
+    fn test_parse_spec_custom_host() {
+        let spec = parse_spec("https://gitea.example.com/org/skills/agents/reviewer@v1").unwrap();
+        assert_eq!(spec.host, "gitea.example.com");
+        assert_eq!(spec.owner, "org");
+        assert_eq!(spec.repo, "skills");
+        assert_eq!(spec.path, "agents/reviewer");
+        assert_eq!(spec.to_string(), "gitea.example.com/org/skills/agents/reviewer@v1");
+        assert_eq!(parse_spec(&spec.to_string()).unwrap().host, "gitea.example.com");
+
+        let spec = parse_spec("jiunbae/agent-skills").unwrap();
+        assert_eq!(spec.host, "github.com");
+        assert_eq!(spec.to_string(), "jiunbae/agent-skills/@main");
+    }
+
+    #[test]
+    fn test_parse_spec_rejects_relative_components() {
+        for spec in [
+            "github.com/../repo",
+            "gitea.example.com/org/..@v1",
+            "owner/../x/repo",
+            "owner/repo/agents/../../x",
+            "https://github.com/owner/repo/./x",
+        ] {
+            assert!(parse_spec(spec).is_err(), "{}", spec);
+        }
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        fs::write(tmp.path().join(".remote-source"), "source: ../../x/repo\n").unwrap();
+        assert!(parse_metadata(tmp.path()).is_err());
+    }
+
+    #[test]
+    fn test_parse_metadata_records_host() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let spec = parse_spec("git.example.com:3000/org/skills/agents/reviewer").unwrap();
+        write_metadata(tmp.path(), &spec).unwrap();
+
+        let parsed = parse_metadata(tmp.path()).unwrap();
+        assert_eq!(parsed.host, "git.example.com:3000");
+        assert_eq!(parsed.path, "agents/reviewer");
+    }
+
+    #[test]
+    fn test_parse_spec_invalid() {
+        assert!(parse_spec("bad-format").is_err());
+    }
+
+    #[test]
+    fn test_parse_metadata_roundtrip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let skill_dir = tmp.path().join("test-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+
+        let spec = RemoteSpec {
+            host: "github.com".to_string(),
+            owner: "jiunbae".to_string(),
+            repo: "agent-skills".to_string(),
+            path: "agents/background-reviewer".to_string(),
+            git_ref: "v2026.02.19.1".to_string(),
+            commit: None,
+            version_req: None,
+            transport: Transport::Archive,
+        };
+        write_metadata(&skill_dir, &spec).unwrap();
+
+        let parsed = parse_metadata(&skill_dir).unwrap();
+        assert_eq!(parsed.owner, "jiunbae");
+        assert_eq!(parsed.repo, "agent-skills");
+        assert_eq!(parsed.path, "agents/background-reviewer");
+        assert_eq!(parsed.git_ref, "v2026.02.19.1");
+        assert_eq!(parsed.commit, None);
+    }
+
+    #[test]
+    fn test_parse_metadata_records_commit() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let skill_dir = tmp.path().join("test-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+
+        let mut spec = parse_spec("jiunbae/agent-skills/agents/background-reviewer").unwrap();
+        spec.commit = Some("0123456789abcdef0123456789abcdef01234567".to_string());
+        write_metadata(&skill_dir, &spec).unwrap();
+
+        let parsed = parse_metadata(&skill_dir).unwrap();
+        assert_eq!(parsed.git_ref, "main");
+        assert_eq!(parsed.commit, spec.commit);
+        assert_eq!(short_commit(parsed.commit.as_deref().unwrap()), "0123456");
+
+        spec.commit = Some("ééééééééé".to_string());
+        write_metadata(&skill_dir, &spec).unwrap();
+        assert!(parse_metadata(&skill_dir).is_err());
+        assert_eq!(short_commit("ééééééééé"), "ééééééé");
+        assert_eq!(short_commit("v1"), "v1");
+    }
+
+    #[test]
+    fn test_parse_spec_version_constraint() {
+        let spec = parse_spec("jiunbae/agent-skills/development/git-commit-pr@^1.2").unwrap();
+        assert_eq!(spec.version_req.as_deref(), Some("^1.2"));
+        assert_eq!(parse_spec("jiunbae/agent-skills@v1.2.0").unwrap().version_req, None);
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let spec = RemoteSpec {
+            git_ref: "v1.3.0".to_string(),
+            ..spec
+        };
+        write_metadata(tmp.path(), &spec).unwrap();
+        let parsed = parse_metadata(tmp.path()).unwrap();
+        assert_eq!(parsed.git_ref, "v1.3.0");
+        assert_eq!(parsed.version_req.as_deref(), Some("^1.2"));
+    }
+
+    #[test]
+    fn test_resolve_commit_full_sha_is_local() {
+        let spec = parse_spec(
+            "jiunbae/agent-skills/agents/background-reviewer@0123456789ABCDEF0123456789abcdef01234567",
+        )
+        .unwrap();
+        assert_eq!(
+            resolve_commit(&spec).unwrap(),
+            "0123456789abcdef0123456789abcdef01234567"
+        );
+    }
+
+    #[test]
+    fn test_with_path_keeps_pin() {
+        let mut spec = parse_spec("jiunbae/agent-skills@v1").unwrap();
+        spec.commit = Some("abc".to_string());
+        let child = spec.with_path("agents/background-reviewer");
+        assert_eq!(child.path, "agents/background-reviewer");
+        assert_eq!(child.git_ref, "v1");
+        assert_eq!(child.download_ref(), "abc");
+    }
+
+    #[test]
+    fn test_parse_metadata_missing_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let skill_dir = tmp.path().join("no-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+        assert!(parse_metadata(&skill_dir).is_err());
+    }
+}
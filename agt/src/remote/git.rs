@@ -0,0 +1,278 @@
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+use super::{RemoteSpec, Transport};
+
+/// Whether input names a git URL to fetch with the system `git`:
+/// `git@host:org/repo.git`, `ssh://`, `git://`, `git+<url>`, or an
+/// `https://` URL ending in `.git`.
+pub fn is_git_spec(input: &str) -> bool {
+    let input = input.trim();
+    input.starts_with("git@")
+        || input.starts_with("ssh://")
+        || input.starts_with("git://")
+        || input.starts_with("git+")
+        || (input.starts_with("https://") && git_suffix_end(input).is_some())
+}
+
+/// Byte offset just past a `.git` that ends the repository URL.
+fn git_suffix_end(input: &str) -> Option<usize> {
+    input.match_indices(".git").find_map(|(i, _)| {
+        let end = i + 4;
+        match input[end..].chars().next() {
+            None | Some('/') | Some('@') => Some(end),
+            _ => None,
+        }
+    })
+}
+
+/// Parse `<url>[/path][@ref]` (URLs ending in `.git`) or `<url>[#path][@ref]`.
+pub(super) fn parse_git_spec(input: &str) -> Result<RemoteSpec> {
+    let input = input.trim();
+    let input = input.strip_prefix("git+").unwrap_or(input);
+
+    let (url, rest) = if let Some((url, rest)) = input.split_once('#') {
+        (url, rest)
+    } else if let Some(end) = git_suffix_end(input) {
+        (&input[..end], input[end..].trim_start_matches('/'))
+    } else {
+        // No path separator: only a trailing @ref after the last '/' or ':'
+        let boundary = input.rfind(['/', ':']).unwrap_or(0);
+        match input.rfind('@').filter(|&at| at > boundary) {
+            Some(at) => (&input[..at], &input[at..]),
+            None => (input, ""),
+        }
+    };
+
+    let (path, git_ref) = match rest.rsplit_once('@') {
+        Some((path, git_ref)) => (path, git_ref),
+        None => (rest, ""),
+    };
+    if url.is_empty() {
+        bail!("Invalid git spec: {}", input);
+    }
+    check_url(url)?;
+    check_ref(git_ref)?;
+
+    Ok(RemoteSpec {
+        path: path.trim_matches('/').to_string(),
+        git_ref: if git_ref.is_empty() { "HEAD" } else { git_ref }.to_string(),
+        transport: Transport::Git(url.to_string()),
+        ..Default::default()
+    })
+}
+
+/// Only https, ssh (including `user@host:path`) and file URLs are fetched;
+/// anything git could read as an option is rejected.
+fn check_url(url: &str) -> Result<()> {
+    // `<transport>::<address>` runs remote helpers such as `ext::`
+    let scp_like = !url.contains("::")
+        && !url.contains("://")
+        && url
+            .split_once(':')
+            .is_some_and(|(host, _)| !host.is_empty() && !host.contains('/'));
+    let allowed = ["https://", "ssh://", "file://"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme));
+    if url.starts_with('-') || !(allowed || scp_like) {
+        bail!("Unsupported git URL '{}' (use https://, ssh:// or file://)", url);
+    }
+    Ok(())
+}
+
+fn check_ref(git_ref: &str) -> Result<()> {
+    if git_ref.starts_with('-') || git_ref.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        bail!("Invalid git ref '{}'", git_ref);
+    }
+    Ok(())
+}
+
+/// Spec form of a git source that `parse_git_spec` reads back.
+pub(super) fn display(url: &str, spec: &RemoteSpec) -> String {
+    let prefix = if is_git_spec(url) { "" } else { "git+" };
+    if spec.path.is_empty() {
+        format!("{}{}@{}", prefix, url, spec.git_ref)
+    } else {
+        format!("{}{}#{}@{}", prefix, url, spec.path, spec.git_ref)
+    }
+}
+
+/// Resolve a branch or tag with `git ls-remote`, preferring peeled tags.
+pub(super) fn resolve_commit(url: &str, git_ref: &str) -> Result<String> {
+    check_url(url)?;
+    check_ref(git_ref)?;
+    let peeled = format!("{}^{{}}", git_ref);
+    let output = run_git(None, &["ls-remote", "--end-of-options", url, git_ref, &peeled])?;
+
+    let mut found = None;
+    for line in output.lines() {
+        let Some((sha, name)) = line.split_once('\t') else {
+            continue;
+        };
+        if name.ends_with("^{}") {
+            return Ok(sha.to_lowercase());
+        }
+        found.get_or_insert_with(|| sha.to_lowercase());
+    }
+    found.context(format!("Ref '{}' not found in {}", git_ref, url))
+}
+
+/// Tag names of the repository at `url`, via `git ls-remote --tags`.
+pub(super) fn list_tags(url: &str) -> Result<Vec<String>> {
+    check_url(url)?;
+    let output = run_git(None, &["ls-remote", "--tags", "--end-of-options", url])?;
+    Ok(output
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter_map(|(_, name)| name.strip_prefix("refs/tags/"))
+        .filter(|name| !name.ends_with("^{}"))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Fetch a single commit with a blob-less partial clone and check out only
+/// `spec.path` (the whole tree for repo-level specs).
+pub(super) fn fetch_dir(url: &str, spec: &RemoteSpec) -> Result<(TempDir, PathBuf)> {
+    check_url(url)?;
+    check_ref(spec.download_ref())?;
+    check_ref(&spec.git_ref)?;
+    let tmp_dir = TempDir::new().context("Failed to create temp directory")?;
+    let repo = tmp_dir.path().join("repo");
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message(format!("Fetching {}@{} with git...", url, spec.git_ref));
+    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+
+    let result = (|| -> Result<()> {
+        run_git(None, &["init", "--quiet", &repo.to_string_lossy()])?;
+        run_git(Some(&repo), &["remote", "add", "origin", "--end-of-options", url])?;
+        if !spec.path.is_empty() {
+            run_git(
+                Some(&repo),
+                &["sparse-checkout", "set", "--no-cone", &format!("/{}", spec.path)],
+            )?;
+        }
+        let fetch = |git_ref: &str| {
+            run_git(
+                Some(&repo),
+                &[
+                    "fetch",
+                    "--quiet",
+                    "--depth",
+                    "1",
+                    "--filter=blob:none",
+                    "origin",
+                    "--end-of-options",
+                    git_ref,
+                ],
+            )
+        };
+        // Servers may refuse fetching a bare SHA; fall back to the ref and
+        // accept it only if it still points at the pinned commit
+        if let Err(e) = fetch(spec.download_ref()) {
+            if spec.commit.is_none() {
+                return Err(e);
+            }
+            fetch(&spec.git_ref)?;
+        }
+        run_git(Some(&repo), &["checkout", "--quiet", "FETCH_HEAD"])?;
+        if let Some(commit) = &spec.commit {
+            let head = run_git(Some(&repo), &["rev-parse", "HEAD"])?;
+            if head.trim() != commit {
+                bail!("{} no longer points at pinned commit {}", spec.git_ref, commit);
+            }
+        }
+        Ok(())
+    })();
+    spinner.finish_and_clear();
+    result.context(format!(
+        "git fetch failed: {}@{}\n\
+         Check that the URL is reachable and your SSH keys or credentials are set up.",
+        url, spec.git_ref
+    ))?;
+
+    let target_path = repo.join(&spec.path);
+    if !target_path.exists() {
+        bail!("Path not found: {} in {}@{}", spec.path, url, spec.git_ref);
+    }
+    Ok((tmp_dir, target_path))
+}
+
+fn run_git(dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut cmd = Command::new("git");
+    if let Some(dir) = dir {
+        cmd.arg("-C").arg(dir);
+    }
+    let output = cmd
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Failed to run git — is it installed?")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git_url(spec: &RemoteSpec) -> &str {
+        match &spec.transport {
+            Transport::Git(url) => url,
+            _ => panic!("not a git spec"),
+        }
+    }
+
+    #[test]
+    fn test_parse_git_spec() {
+        let spec = parse_git_spec("git@git.example.com:org/skills.git/agents/reviewer@v1").unwrap();
+        assert_eq!(git_url(&spec), "git@git.example.com:org/skills.git");
+        assert_eq!(spec.path, "agents/reviewer");
+        assert_eq!(spec.git_ref, "v1");
+
+        let spec = parse_git_spec("git@git.example.com:org/skills.git").unwrap();
+        assert_eq!(spec.path, "");
+        assert_eq!(spec.git_ref, "HEAD");
+
+        let spec = parse_git_spec("git+https://host/org/skills#agents/reviewer@main").unwrap();
+        assert_eq!(git_url(&spec), "https://host/org/skills");
+        assert_eq!(spec.path, "agents/reviewer");
+
+        let spec = parse_git_spec("ssh://git@host:2222/org/skills@dev").unwrap();
+        assert_eq!(git_url(&spec), "ssh://git@host:2222/org/skills");
+        assert_eq!(spec.git_ref, "dev");
+    }
+
+    #[test]
+    fn test_git_display_roundtrip() {
+        for input in [
+            "git@host:org/skills.git#agents/reviewer@v1",
+            "git+https://host/org/skills#agents/reviewer@main",
+            "ssh://git@host/org/skills@HEAD",
+        ] {
+            let spec = parse_git_spec(input).unwrap();
+            let Transport::Git(url) = &spec.transport else {
+                panic!("not a git spec");
+            };
+            assert_eq!(display(url, &spec), input);
+        }
+    }
+
+    #[test]
+    fn test_rejects_option_like_urls_and_refs() {
+        assert!(parse_git_spec("git+--upload-pack=touch /tmp/x#a@main").is_err());
+        assert!(parse_git_spec("git+https://host/org/skills@--upload-pack=x").is_err());
+        assert!(parse_git_spec("git+ext::sh -c touch% /tmp/x").is_err());
+        assert!(parse_git_spec("git://host/org/skills.git").is_err());
+        assert!(parse_git_spec("git+file:///srv/skills@main").is_ok());
+        assert!(resolve_commit("--upload-pack=x", "HEAD").is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use super::provider::{encode_component, env_token, host_listed};
+use super::{Provider, RemoteSpec};
+
+/// Gitea and Forgejo — API v1. Used for `GITEA_HOST` hosts and any host not
+/// recognised as GitHub or GitLab.
+pub struct Gitea {
+    pub host: String,
+}
+
+impl Provider for Gitea {
+    fn name(&self) -> &'static str {
+        "Gitea"
+    }
+
+    /// `GITEA_TOKEN`, then `FORGEJO_TOKEN`, for the hosts in `GITEA_HOST`.
+    fn token(&self) -> Option<String> {
+        if !host_listed("GITEA_HOST", &self.host) {
+            return None;
+        }
+        env_token(&["GITEA_TOKEN", "FORGEJO_TOKEN"])
+    }
+
+    fn authorize(&self, req: ureq::Request, token: &str) -> ureq::Request {
+        req.set("Authorization", &format!("token {}", token))
+    }
+
+    fn commit_request(&self, spec: &RemoteSpec) -> (String, &'static str) {
+        (
+            format!(
+                "https://{}/api/v1/repos/{}/{}/commits?sha={}&limit=1&stat=false&files=false",
+                self.host,
+                spec.owner,
+                spec.repo,
+                encode_component(&spec.git_ref)
+            ),
+            "application/json",
+        )
+    }
+
+    fn parse_commit(&self, body: &str) -> Option<String> {
+        let value: serde_json::Value = serde_json::from_str(body).ok()?;
+        value[0]["sha"].as_str().map(str::to_string)
+    }
+
+    fn tags_url(&self, spec: &RemoteSpec) -> String {
+        format!(
+            "https://{}/api/v1/repos/{}/{}/tags?limit=50",
+            self.host, spec.owner, spec.repo
+        )
+    }
+
+    fn archive_urls(&self, spec: &RemoteSpec, git_ref: &str, _pinned: bool) -> Vec<String> {
+        vec![
+            format!(
+                "https://{}/api/v1/repos/{}/{}/archive/{}.tar.gz",
+                self.host, spec.owner, spec.repo, git_ref
+            ),
+            format!(
+                "https://{}/{}/{}/archive/{}.tar.gz",
+                self.host, spec.owner, spec.repo, git_ref
+            ),
+        ]
+    }
+
+    fn raw_url(&self, spec: &RemoteSpec, git_ref: &str) -> String {
+        format!(
+            "https://{}/api/v1/repos/{}/{}/raw/{}?ref={}",
+            self.host,
+            spec.owner,
+            spec.repo,
+            spec.path,
+            encode_component(git_ref)
+        )
+    }
+
+    fn auth_hint(&self) -> String {
+        format!(
+            "If this is a private repo, set an access token for {}:\n  \
+             GITEA_TOKEN=<token>    (environment variable, read:repository scope)\n  \
+             GITEA_HOST={}   (hosts the token is sent to)",
+            self.host,
+            self.host
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitea_commit_response() {
+        let gitea = Gitea {
+            host: "git.example.com".to_string(),
+        };
+        assert_eq!(
+            gitea.parse_commit(r#"[{"sha": "abc123", "url": "x"}]"#),
+            Some("abc123".to_string())
+        );
+        assert_eq!(gitea.parse_commit("[]"), None);
+    }
+}
@@ -1,445 +1,89 @@
-use anyhow::{bail, Context, Result};
-use flate2::read::GzDecoder;
-use std::fs;
-use std::io::Read;
-use std::path::{Path, PathBuf};
 use std::process::Command;
-use tar::Archive;
-use tempfile::TempDir;
 
-/// Resolve a GitHub token from environment or `gh auth token`.
-fn get_github_token() -> Option<String> {
-    if let Ok(t) = std::env::var("GITHUB_TOKEN") {
-        if !t.is_empty() {
-            return Some(t);
-        }
-    }
-    if let Ok(t) = std::env::var("GH_TOKEN") {
-        if !t.is_empty() {
-            return Some(t);
-        }
-    }
-    // Fall back to gh CLI
-    Command::new("gh")
-        .args(["auth", "token"])
-        .output()
-        .ok()
-        .filter(|o| o.status.success())
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-        .filter(|t| !t.is_empty())
-}
+use super::provider::env_token;
+use super::{Provider, RemoteSpec};
 
-/// Build a ureq request with optional auth header.
-fn authed_get(url: &str) -> ureq::Request {
-    let req = ureq::get(url);
-    if let Some(token) = get_github_token() {
-        req.set("Authorization", &format!("Bearer {}", token))
-    } else {
-        req
-    }
-}
+/// github.com — REST API, codeload archives and raw.githubusercontent.com.
+pub struct GitHub;
 
-/// Parsed remote specification
-#[derive(Debug, Clone)]
-pub struct RemoteSpec {
-    pub owner: String,
-    pub repo: String,
-    pub path: String,
-    pub git_ref: String,
-}
-
-impl std::fmt::Display for RemoteSpec {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}/{}/{}@{}",
-            self.owner, self.repo, self.path, self.git_ref
-        )
+impl Provider for GitHub {
+    fn name(&self) -> &'static str {
+        "GitHub"
     }
-}
-
-/// Parse "owner/repo/path[@ref]" into a RemoteSpec.
-/// Also accepts URL-style input: github.com/owner/repo/path, https://github.com/owner/repo/path
-pub fn parse_spec(spec: &str) -> Result<RemoteSpec> {
-    let spec = spec.trim();
-    // Strip common URL prefixes
-    let spec = spec
-        .strip_prefix("https://")
-        .or_else(|| spec.strip_prefix("http://"))
-        .unwrap_or(spec);
-    let spec = spec
-        .strip_prefix("github.com/")
-        .unwrap_or(spec);
-    let spec = spec.trim_end_matches('/');
-
-    // Extract @ref suffix
-    let (path_part, git_ref) = if let Some(at_pos) = spec.rfind('@') {
-        (&spec[..at_pos], spec[at_pos + 1..].to_string())
-    } else {
-        (spec, "main".to_string())
-    };
 
-    let parts: Vec<&str> = path_part.split('/').collect();
-    if parts.len() < 2 {
-        bail!(
-            "Invalid format: {}\nExpected: owner/repo[/path/to/skill][@ref]\n\
-             Examples:\n  jiunbae/agent-skills/agents/background-reviewer\n  \
-             jiunbae/agent-skills  (browse all skills in repo)",
-            spec
-        );
+    /// `GITHUB_TOKEN`, `GH_TOKEN`, then `gh auth token`.
+    fn token(&self) -> Option<String> {
+        env_token(&["GITHUB_TOKEN", "GH_TOKEN"]).or_else(|| {
+            Command::new("gh")
+                .args(["auth", "token"])
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                .filter(|t| !t.is_empty())
+        })
     }
 
-    Ok(RemoteSpec {
-        owner: parts[0].to_string(),
-        repo: parts[1].to_string(),
-        path: if parts.len() > 2 {
-            parts[2..].join("/")
-        } else {
-            String::new()
-        },
-        git_ref,
-    })
-}
-
-const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10 MB
-
-/// Download a single file from raw.githubusercontent.com
-pub fn fetch_file(spec: &RemoteSpec) -> Result<Vec<u8>> {
-    let spinner = indicatif::ProgressBar::new_spinner();
-    spinner.set_message(format!("Fetching {}...", spec.path));
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
-
-    let url = format!(
-        "https://raw.githubusercontent.com/{}/{}/{}/{}",
-        spec.owner, spec.repo, spec.git_ref, spec.path
-    );
-
-    let response = authed_get(&url)
-        .call()
-        .context(format!("Failed to download: {}", url))?;
-
-    let mut body = Vec::new();
-    response
-        .into_reader()
-        .take(MAX_FILE_SIZE)
-        .read_to_end(&mut body)
-        .context("Failed to read response")?;
-
-    spinner.finish_and_clear();
-    Ok(body)
-}
-
-/// Download a directory from GitHub tarball, extract to a temp directory.
-/// Returns (TempDir, path_to_extracted_content).
-/// The TempDir must be kept alive by the caller — dropping it cleans up.
-pub fn fetch_dir(spec: &RemoteSpec) -> Result<(TempDir, PathBuf)> {
-    let spinner = indicatif::ProgressBar::new_spinner();
-    spinner.set_message(format!("Downloading {}/{}@{}...", spec.owner, spec.repo, spec.git_ref));
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
-
-    let tmp_dir = TempDir::new().context("Failed to create temp directory")?;
-
-    // Try API tarball (works with auth for private repos), then archive URLs
-    let urls = [
-        format!(
-            "https://api.github.com/repos/{}/{}/tarball/{}",
-            spec.owner, spec.repo, spec.git_ref
-        ),
-        format!(
-            "https://github.com/{}/{}/archive/refs/tags/{}.tar.gz",
-            spec.owner, spec.repo, spec.git_ref
-        ),
-        format!(
-            "https://github.com/{}/{}/archive/refs/heads/{}.tar.gz",
-            spec.owner, spec.repo, spec.git_ref
-        ),
-    ];
-
-    let mut extracted_root: Option<PathBuf> = None;
-
-    for url in &urls {
-        // Clean previous attempt
-        if let Ok(entries) = fs::read_dir(tmp_dir.path()) {
-            for entry in entries.flatten() {
-                let _ = fs::remove_dir_all(entry.path());
-            }
-        }
-
-        let response = match authed_get(url)
-            .set("Accept", "application/vnd.github+json")
-            .set("User-Agent", "agt-cli")
-            .call()
-        {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
-
-        const MAX_TARBALL_SIZE: u64 = 50 * 1024 * 1024; // 50 MB
-        let decoder = GzDecoder::new(response.into_reader().take(MAX_TARBALL_SIZE));
-        let mut archive = Archive::new(decoder);
-
-        if archive.unpack(tmp_dir.path()).is_err() {
-            continue;
-        }
-
-        // Find extracted root directory
-        if let Ok(entries) = fs::read_dir(tmp_dir.path()) {
-            for entry in entries.flatten() {
-                if entry.path().is_dir() {
-                    extracted_root = Some(entry.path());
-                    break;
-                }
-            }
-        }
-
-        if extracted_root.is_some() {
-            break;
-        }
+    fn authorize(&self, req: ureq::Request, token: &str) -> ureq::Request {
+        req.set("Authorization", &format!("Bearer {}", token))
     }
 
-    let root = extracted_root.context(format!(
-        "Download failed: {}/{}@{}\n\
-         If this is a private repo, ensure authentication is available:\n  \
-         gh auth login          (gh CLI)\n  \
-         GITHUB_TOKEN=<token>   (environment variable)",
-        spec.owner, spec.repo, spec.git_ref
-    ))?;
-
-    let target_path = if spec.path.is_empty() {
-        root.clone()
-    } else {
-        let p = root.join(&spec.path);
-        if !p.exists() {
-            bail!(
-                "Path not found: {} in {}/{}@{}",
-                spec.path,
-                spec.owner,
-                spec.repo,
-                spec.git_ref
-            );
-        }
-        p
-    };
-
-    spinner.finish_and_clear();
-    Ok((tmp_dir, target_path))
-}
-
-/// Write .remote-source metadata file
-pub fn write_metadata(target: &Path, spec: &RemoteSpec) -> Result<()> {
-    let metadata_path = if target.is_dir() {
-        target.join(".remote-source")
-    } else {
-        let stem = target
-            .file_stem()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_string();
-        target.with_file_name(format!("{}.remote-source", stem))
-    };
-
-    let content = format!(
-        "source: {}/{}/{}\nref: {}\ninstalled: {}\n",
-        spec.owner,
-        spec.repo,
-        spec.path,
-        spec.git_ref,
-        chrono_like_now()
-    );
-
-    fs::write(&metadata_path, content).context("Failed to write remote metadata")?;
-    Ok(())
-}
-
-/// Parse .remote-source metadata file back into a RemoteSpec.
-pub fn parse_metadata(skill_dir: &Path) -> Result<RemoteSpec> {
-    let metadata_path = skill_dir.join(".remote-source");
-    let content = fs::read_to_string(&metadata_path)
-        .context(format!("Failed to read {}", metadata_path.display()))?;
-
-    let mut source = String::new();
-    let mut git_ref = "main".to_string();
-
-    for line in content.lines() {
-        let line = line.trim();
-        if let Some(val) = line.strip_prefix("source:") {
-            source = val.trim().to_string();
-        } else if let Some(val) = line.strip_prefix("ref:") {
-            git_ref = val.trim().to_string();
-        }
+    fn commit_request(&self, spec: &RemoteSpec) -> (String, &'static str) {
+        (
+            format!(
+                "https://api.github.com/repos/{}/{}/commits/{}",
+                spec.owner, spec.repo, spec.git_ref
+            ),
+            "application/vnd.github.sha",
+        )
     }
 
-    if source.is_empty() {
-        bail!(
-            "Invalid .remote-source: missing 'source' field in {}",
-            metadata_path.display()
-        );
+    fn parse_commit(&self, body: &str) -> Option<String> {
+        Some(body.trim().to_string())
     }
 
-    // source is "owner/repo/path" — split into parts
-    let parts: Vec<&str> = source.splitn(3, '/').collect();
-    if parts.len() < 2 {
-        bail!("Invalid source format in .remote-source: {}", source);
+    fn tags_url(&self, spec: &RemoteSpec) -> String {
+        format!(
+            "https://api.github.com/repos/{}/{}/tags?per_page=100",
+            spec.owner, spec.repo
+        )
     }
 
-    Ok(RemoteSpec {
-        owner: parts[0].to_string(),
-        repo: parts[1].to_string(),
-        path: if parts.len() > 2 {
-            parts[2].to_string()
-        } else {
-            String::new()
-        },
-        git_ref,
-    })
-}
-
-fn chrono_like_now() -> String {
-    use std::time::SystemTime;
-    let duration = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default();
-    let secs = duration.as_secs();
-    // Simple UTC timestamp without chrono dependency
-    let days = secs / 86400;
-    let time_secs = secs % 86400;
-    let hours = time_secs / 3600;
-    let mins = (time_secs % 3600) / 60;
-    let s = time_secs % 60;
-
-    // Approximate date calculation (good enough for metadata)
-    let mut y = 1970i64;
-    let mut remaining_days = days as i64;
-    loop {
-        let days_in_year = if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) {
-            366
+    fn archive_urls(&self, spec: &RemoteSpec, git_ref: &str, pinned: bool) -> Vec<String> {
+        // API tarball works with auth for private repos; archive URLs are the fallback
+        let mut urls = vec![format!(
+            "https://api.github.com/repos/{}/{}/tarball/{}",
+            spec.owner, spec.repo, git_ref
+        )];
+        if pinned {
+            urls.push(format!(
+                "https://github.com/{}/{}/archive/{}.tar.gz",
+                spec.owner, spec.repo, git_ref
+            ));
         } else {
-            365
-        };
-        if remaining_days < days_in_year {
-            break;
+            urls.push(format!(
+                "https://github.com/{}/{}/archive/refs/tags/{}.tar.gz",
+                spec.owner, spec.repo, git_ref
+            ));
+            urls.push(format!(
+                "https://github.com/{}/{}/archive/refs/heads/{}.tar.gz",
+                spec.owner, spec.repo, git_ref
+            ));
         }
-        remaining_days -= days_in_year;
-        y += 1;
-    }
-    let is_leap = y % 4 == 0 && (y % 100 != 0 || y % 400 == 0);
-    let month_days = [
-        31,
-        if is_leap { 29 } else { 28 },
-        31,
-        30,
-        31,
-        30,
-        31,
-        31,
-        30,
-        31,
-        30,
-        31,
-    ];
-    let mut m = 0;
-    for (i, &md) in month_days.iter().enumerate() {
-        if remaining_days < md as i64 {
-            m = i + 1;
-            break;
-        }
-        remaining_days -= md as i64;
-    }
-    if m == 0 {
-        m = 12;
-        remaining_days = 0;
+        urls
     }
-    let d = remaining_days + 1;
 
-    format!(
-        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-        y, m, d, hours, mins, s
-    )
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_spec_basic() {
-        let spec = parse_spec("jiunbae/agent-skills/agents/background-reviewer").unwrap();
-        assert_eq!(spec.owner, "jiunbae");
-        assert_eq!(spec.repo, "agent-skills");
-        assert_eq!(spec.path, "agents/background-reviewer");
-        assert_eq!(spec.git_ref, "main");
-    }
-
-    #[test]
-    fn test_parse_spec_with_ref() {
-        let spec =
-            parse_spec("jiunbae/agent-skills/agents/background-reviewer@v2026.02.19.1").unwrap();
-        assert_eq!(spec.path, "agents/background-reviewer");
-        assert_eq!(spec.git_ref, "v2026.02.19.1");
-    }
-
-    #[test]
-    fn test_parse_spec_persona() {
-        let spec = parse_spec("jiunbae/agent-skills/personas/security-reviewer").unwrap();
-        assert_eq!(spec.path, "personas/security-reviewer");
-    }
-
-    #[test]
-    fn test_parse_spec_url_prefix() {
-        let spec = parse_spec("https://github.com/jiunbae/agent-skills/agents/background-reviewer").unwrap();
-        assert_eq!(spec.owner, "jiunbae");
-        assert_eq!(spec.repo, "agent-skills");
-        assert_eq!(spec.path, "agents/background-reviewer");
-
-        let spec = parse_spec("github.com/jiunbae/agent-skills/context/context-manager").unwrap();
-        assert_eq!(spec.owner, "jiunbae");
-        assert_eq!(spec.path, "context/context-manager");
-    }
-
-    #[test]
-    fn test_parse_spec_repo_only() {
-        let spec = parse_spec("jiunbae/agent-skills").unwrap();
-        assert_eq!(spec.owner, "jiunbae");
-        assert_eq!(spec.repo, "agent-skills");
-        assert_eq!(spec.path, "");
-        assert_eq!(spec.git_ref, "main");
-
-        let spec = parse_spec("github.com/jiunbae/agent-skills").unwrap();
-        assert_eq!(spec.owner, "jiunbae");
-        assert_eq!(spec.path, "");
-    }
-
-    #[test]
-    fn test_parse_spec_invalid() {
-        assert!(parse_spec("bad-format").is_err());
-    }
-
-    #[test]
-    fn test_parse_metadata_roundtrip() {
-        let tmp = tempfile::TempDir::new().unwrap();
-        let skill_dir = tmp.path().join("test-skill");
-        fs::create_dir_all(&skill_dir).unwrap();
-
-        let spec = RemoteSpec {
-            owner: "jiunbae".to_string(),
-            repo: "agent-skills".to_string(),
-            path: "agents/background-reviewer".to_string(),
-            git_ref: "v2026.02.19.1".to_string(),
-        };
-        write_metadata(&skill_dir, &spec).unwrap();
-
-        let parsed = parse_metadata(&skill_dir).unwrap();
-        assert_eq!(parsed.owner, "jiunbae");
-        assert_eq!(parsed.repo, "agent-skills");
-        assert_eq!(parsed.path, "agents/background-reviewer");
-        assert_eq!(parsed.git_ref, "v2026.02.19.1");
+    fn raw_url(&self, spec: &RemoteSpec, git_ref: &str) -> String {
+        format!(
+            "https://raw.githubusercontent.com/{}/{}/{}/{}",
+            spec.owner, spec.repo, git_ref, spec.path
+        )
     }
 
-    #[test]
-    fn test_parse_metadata_missing_file() {
-        let tmp = tempfile::TempDir::new().unwrap();
-        let skill_dir = tmp.path().join("no-skill");
-        fs::create_dir_all(&skill_dir).unwrap();
-        assert!(parse_metadata(&skill_dir).is_err());
+    fn auth_hint(&self) -> String {
+        "If this is a private repo, ensure authentication is available:\n  \
+         gh auth login          (gh CLI)\n  \
+         GITHUB_TOKEN=<token>   (environment variable)"
+            .to_string()
     }
 }
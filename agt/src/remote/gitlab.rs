@@ -0,0 +1,114 @@
+use super::provider::{encode_component, env_token, host_listed};
+use super::{Provider, RemoteSpec};
+
+/// GitLab (gitlab.com or self-hosted) — API v4.
+pub struct GitLab {
+    pub host: String,
+}
+
+impl GitLab {
+    fn project_api(&self, spec: &RemoteSpec) -> String {
+        format!(
+            "https://{}/api/v4/projects/{}",
+            self.host,
+            encode_component(&format!("{}/{}", spec.owner, spec.repo))
+        )
+    }
+}
+
+impl Provider for GitLab {
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    /// `GITLAB_TOKEN`, for gitlab.com and the hosts in `GITLAB_HOST`.
+    fn token(&self) -> Option<String> {
+        if self.host != "gitlab.com" && !host_listed("GITLAB_HOST", &self.host) {
+            return None;
+        }
+        env_token(&["GITLAB_TOKEN"])
+    }
+
+    fn authorize(&self, req: ureq::Request, token: &str) -> ureq::Request {
+        req.set("PRIVATE-TOKEN", token)
+    }
+
+    fn commit_request(&self, spec: &RemoteSpec) -> (String, &'static str) {
+        (
+            format!(
+                "{}/repository/commits/{}",
+                self.project_api(spec),
+                encode_component(&spec.git_ref)
+            ),
+            "application/json",
+        )
+    }
+
+    fn parse_commit(&self, body: &str) -> Option<String> {
+        let value: serde_json::Value = serde_json::from_str(body).ok()?;
+        value["id"].as_str().map(str::to_string)
+    }
+
+    fn tags_url(&self, spec: &RemoteSpec) -> String {
+        format!("{}/repository/tags?per_page=100", self.project_api(spec))
+    }
+
+    fn archive_urls(&self, spec: &RemoteSpec, git_ref: &str, _pinned: bool) -> Vec<String> {
+        vec![format!(
+            "{}/repository/archive.tar.gz?sha={}",
+            self.project_api(spec),
+            encode_component(git_ref)
+        )]
+    }
+
+    fn raw_url(&self, spec: &RemoteSpec, git_ref: &str) -> String {
+        format!(
+            "{}/repository/files/{}/raw?ref={}",
+            self.project_api(spec),
+            encode_component(&spec.path),
+            encode_component(git_ref)
+        )
+    }
+
+    fn auth_hint(&self) -> String {
+        format!(
+            "If this is a private repo, set a personal access token for {}:\n  \
+             GITLAB_TOKEN=<token>   (environment variable, read_repository scope)\n  \
+             GITLAB_HOST={}   (self-hosted instances the token is sent to)",
+            self.host,
+            self.host
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitlab_urls() {
+        let gitlab = GitLab {
+            host: "gitlab.com".to_string(),
+        };
+        let spec = RemoteSpec {
+            host: "gitlab.com".to_string(),
+            owner: "group".to_string(),
+            repo: "skills".to_string(),
+            path: "agents/reviewer/SKILL.md".to_string(),
+            git_ref: "main".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            gitlab.archive_urls(&spec, "main", false),
+            vec!["https://gitlab.com/api/v4/projects/group%2Fskills/repository/archive.tar.gz?sha=main"]
+        );
+        assert_eq!(
+            gitlab.raw_url(&spec, "main"),
+            "https://gitlab.com/api/v4/projects/group%2Fskills/repository/files/agents%2Freviewer%2FSKILL.md/raw?ref=main"
+        );
+        assert_eq!(
+            gitlab.parse_commit(r#"{"id": "abc123", "short_id": "abc"}"#),
+            Some("abc123".to_string())
+        );
+    }
+}
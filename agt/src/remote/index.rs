@@ -0,0 +1,231 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::{fetch_file, RemoteSpec};
+use crate::{config, frontmatter, ui, util};
+
+/// Catalog a skills repository publishes at its root (or at the `--from` path).
+pub const INDEX_FILE: &str = "index.json";
+/// `version` of the index format this build reads and writes.
+pub const INDEX_VERSION: u32 = 1;
+
+/// `index.json`: what a library offers, readable without downloading it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogIndex {
+    pub version: u32,
+    #[serde(default)]
+    pub skills: Vec<IndexSkill>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<IndexProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexSkill {
+    pub name: String,
+    pub group: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// `util::hash_dir` of the skill directory
+    pub hash: String,
+}
+
+/// A profile resolved at build time, so clients need no profiles.yml.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexProfile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// `group/name`, with `@<constraint>` where the profile pins one
+    pub skills: Vec<String>,
+}
+
+impl CatalogIndex {
+    /// Skills and version constraints of a profile; `all` is every skill.
+    /// Names come from a remote file, so each part is validated as a path
+    /// component.
+    pub fn resolve_profile(&self, name: &str) -> Result<config::ResolvedProfile> {
+        if name == "all" {
+            let skills = self
+                .skills
+                .iter()
+                .map(|s| skill_pair(&s.group, &s.name))
+                .collect::<Result<_>>()?;
+            return Ok(config::ResolvedProfile {
+                name: name.to_string(),
+                description: "All available skills".to_string(),
+                skills,
+                versions: BTreeMap::new(),
+            });
+        }
+        let profile = self
+            .profiles
+            .iter()
+            .find(|p| p.name == name)
+            .context(format!("Profile '{}' is not in the index", name))?;
+
+        let mut skills = Vec::new();
+        let mut versions = BTreeMap::new();
+        for entry in &profile.skills {
+            let (key, req) = match entry.split_once('@') {
+                Some((key, req)) => (key, Some(req)),
+                None => (entry.as_str(), None),
+            };
+            let (group, skill) = key
+                .split_once('/')
+                .context(format!("Invalid skill '{}' in indexed profile '{}'", entry, name))?;
+            skills.push(skill_pair(group, skill)?);
+            if let Some(req) = req {
+                versions.insert(key.to_string(), req.to_string());
+            }
+        }
+        Ok(config::ResolvedProfile {
+            name: profile.name.clone(),
+            description: profile.description.clone(),
+            skills,
+            versions,
+        })
+    }
+}
+
+/// `(group, name)` of an indexed skill, rejecting names that are not plain
+/// path components.
+fn skill_pair(group: &str, name: &str) -> Result<(String, String)> {
+    util::validate_name(group)?;
+    util::validate_name(name)?;
+    Ok((group.to_string(), name.to_string()))
+}
+
+/// Build the index of a library from its skill groups and profiles.
+pub fn build_index(source_dir: &Path) -> Result<CatalogIndex> {
+    let mut skills = Vec::new();
+    for group in config::skill_groups(source_dir) {
+        for name in config::skills_in_group(source_dir, &group) {
+            let dir = source_dir.join(&group).join(&name);
+            let skill_md = dir.join("SKILL.md");
+            let content = fs::read_to_string(&skill_md)
+                .context(format!("Failed to read {}", skill_md.display()))?;
+            let (fm, _) = frontmatter::parse(&content)
+                .context(format!("Invalid frontmatter in {}", skill_md.display()))?;
+            skills.push(IndexSkill {
+                hash: util::hash_dir(&dir)?,
+                name,
+                group: group.clone(),
+                description: fm.description.unwrap_or_default().trim().to_string(),
+                tags: fm.tags.unwrap_or_default(),
+                version: fm.version,
+            });
+        }
+    }
+
+    let profiles = config::library_profiles(source_dir)?
+        .into_iter()
+        .map(|profile| IndexProfile {
+            skills: profile
+                .skills
+                .iter()
+                .map(|(group, skill)| {
+                    let key = format!("{}/{}", group, skill);
+                    match profile.versions.get(&key) {
+                        Some(req) => format!("{}@{}", key, req),
+                        None => key,
+                    }
+                })
+                .collect(),
+            name: profile.name,
+            description: profile.description,
+        })
+        .collect();
+
+    Ok(CatalogIndex {
+        version: INDEX_VERSION,
+        skills,
+        profiles,
+    })
+}
+
+/// Parse `index.json` content, rejecting formats newer than this build.
+pub fn parse_index(content: &[u8]) -> Result<CatalogIndex> {
+    let index: CatalogIndex = serde_json::from_slice(content).context("Invalid index.json")?;
+    if index.version > INDEX_VERSION {
+        bail!(
+            "index.json version {} is newer than this agt supports ({})",
+            index.version,
+            INDEX_VERSION
+        );
+    }
+    Ok(index)
+}
+
+/// The index published with a repository-level spec, if there is a usable one.
+/// A missing index is normal; a broken one is reported and skipped.
+pub fn fetch_index(spec: &RemoteSpec) -> Option<CatalogIndex> {
+    let path = if spec.path.is_empty() {
+        INDEX_FILE.to_string()
+    } else {
+        format!("{}/{}", spec.path.trim_end_matches('/'), INDEX_FILE)
+    };
+    let content = fetch_file(&spec.with_path(path)).ok()?;
+    match parse_index(&content) {
+        Ok(index) => Some(index),
+        Err(e) => {
+            ui::warn(&format!("Ignoring index of {}: {:#}", spec.repo_label(), e));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_and_parse_index() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        for (group, name, extra) in [
+            ("development", "git-commit-pr", "version: 1.2.0\ntags: [git]\n"),
+            ("context", "context-manager", ""),
+        ] {
+            let dir = root.join(group).join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: {} skill\n{}---\nbody\n", name, name, extra),
+            )
+            .unwrap();
+        }
+        fs::write(
+            root.join("profiles.yml"),
+            "review:\n  description: Review\n  skills: [development/git-commit-pr@^1.2]\n",
+        )
+        .unwrap();
+
+        let index = build_index(root).unwrap();
+        let names: Vec<&str> = index.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["context-manager", "git-commit-pr"]);
+        assert_eq!(index.skills[1].version.as_deref(), Some("1.2.0"));
+        assert!(index.skills[1].hash.starts_with("sha256:"));
+
+        let parsed = parse_index(serde_json::to_string(&index).unwrap().as_bytes()).unwrap();
+        assert_eq!(parsed, index);
+        let review = parsed.resolve_profile("review").unwrap();
+        assert_eq!(review.skills, [("development".to_string(), "git-commit-pr".to_string())]);
+        assert_eq!(review.versions["development/git-commit-pr"], "^1.2");
+        assert_eq!(parsed.resolve_profile("all").unwrap().skills.len(), 2);
+
+        assert!(parse_index(b"{\"version\": 99, \"skills\": []}").is_err());
+
+        let mut hostile = parsed.clone();
+        hostile.profiles[0].skills = vec!["../../etc/git-commit-pr".to_string()];
+        assert!(hostile.resolve_profile("review").is_err());
+        hostile.skills[0].group = "..".to_string();
+        assert!(hostile.resolve_profile("all").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tar::Archive;
+use tempfile::TempDir;
+
+use super::{RemoteSpec, Transport};
+
+/// Whether `--from` input names a local directory or tarball rather than a
+/// `[host/]owner/repo` spec: `file://` URLs, absolute or `./`, `../`, `~/`
+/// paths, and `.tar.gz`/`.tgz` bundles.
+pub fn is_local_spec(input: &str) -> bool {
+    let input = input.trim();
+    input.starts_with("file://")
+        || input.starts_with('/')
+        || input.starts_with("./")
+        || input.starts_with("../")
+        || input.starts_with("~/")
+        || input == "."
+        || input == ".."
+        || is_tarball_name(input.split('#').next().unwrap_or(input))
+}
+
+fn is_tarball_name(name: &str) -> bool {
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// Parse `<dir-or-bundle>[#sub/path]` into a spec.
+/// A directory holding SKILL.md or PERSONA.md is taken as a single item
+/// inside its grandparent, mirroring `owner/repo/group/skill` remote specs.
+pub(super) fn parse_local_spec(input: &str) -> Result<RemoteSpec> {
+    let input = input.trim();
+    let (location, subpath) = match input.split_once('#') {
+        Some((location, subpath)) => (location, subpath.trim_matches('/')),
+        None => (input, ""),
+    };
+    let location = location.strip_prefix("file://").unwrap_or(location);
+    let location = match location.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .context("Cannot resolve ~: home directory unknown")?
+            .join(rest),
+        None => PathBuf::from(location),
+    };
+
+    let root = fs::canonicalize(&location)
+        .context(format!("Local source not found: {}", location.display()))?;
+
+    if root.is_file() {
+        if !is_tarball_name(&root.to_string_lossy()) {
+            bail!(
+                "Unsupported local source: {} (expected a directory or .tar.gz bundle)",
+                root.display()
+            );
+        }
+        return Ok(local_spec(Transport::Tarball(root), subpath));
+    }
+
+    let is_item = root.join("SKILL.md").is_file() || root.join("PERSONA.md").is_file();
+    if subpath.is_empty() && is_item {
+        let name = root.file_name().context("Invalid local path")?;
+        let parent = root.parent().context("Invalid local path")?;
+        return Ok(match (parent.file_name(), parent.parent()) {
+            (Some(group), Some(base)) => local_spec(
+                Transport::Local(base.to_path_buf()),
+                &format!("{}/{}", group.to_string_lossy(), name.to_string_lossy()),
+            ),
+            _ => local_spec(
+                Transport::Local(parent.to_path_buf()),
+                &name.to_string_lossy(),
+            ),
+        });
+    }
+
+    Ok(local_spec(Transport::Local(root), subpath))
+}
+
+fn local_spec(transport: Transport, path: &str) -> RemoteSpec {
+    RemoteSpec {
+        path: path.to_string(),
+        transport,
+        ..Default::default()
+    }
+}
+
+/// Root of a local source: the directory itself, or a bundle extracted into
+/// the returned TempDir. Bundles with a single top-level directory are
+/// unwrapped, so both `tar -C repo .` and `tar repo/` layouts work.
+pub(super) fn local_root(spec: &RemoteSpec) -> Result<(TempDir, PathBuf)> {
+    let tmp_dir = TempDir::new().context("Failed to create temp directory")?;
+    match &spec.transport {
+        Transport::Local(dir) => {
+            if !dir.is_dir() {
+                bail!("Local source not found: {}", dir.display());
+            }
+            Ok((tmp_dir, dir.clone()))
+        }
+        Transport::Tarball(file) => {
+            let reader = fs::File::open(file)
+                .context(format!("Failed to open {}", file.display()))?;
+            Archive::new(GzDecoder::new(reader))
+                .unpack(tmp_dir.path())
+                .context(format!("Failed to extract {}", file.display()))?;
+            let root = single_top_dir(tmp_dir.path()).unwrap_or_else(|| tmp_dir.path().to_path_buf());
+            Ok((tmp_dir, root))
+        }
+        Transport::Archive | Transport::Git(_) => bail!("{} is not a local source", spec),
+    }
+}
+
+fn single_top_dir(dir: &Path) -> Option<PathBuf> {
+    let entries: Vec<PathBuf> = fs::read_dir(dir).ok()?.flatten().map(|e| e.path()).collect();
+    match entries.as_slice() {
+        [only] if only.is_dir() => Some(only.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_spec() {
+        assert!(is_local_spec("./skills"));
+        assert!(is_local_spec("/srv/agent-skills"));
+        assert!(is_local_spec("file:///srv/agent-skills"));
+        assert!(is_local_spec("bundle.tar.gz"));
+        assert!(!is_local_spec("jiunbae/agent-skills"));
+        assert!(!is_local_spec("gitea.example.com/org/skills@v1"));
+    }
+
+    #[test]
+    fn test_parse_local_skill_dir() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let skill = tmp.path().join("repo/development/my-skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: my-skill\n---\n").unwrap();
+
+        let repo = fs::canonicalize(tmp.path().join("repo")).unwrap();
+        let spec = parse_local_spec(&skill.to_string_lossy()).unwrap();
+        assert_eq!(spec.transport, Transport::Local(repo.clone()));
+        assert_eq!(spec.path, "development/my-skill");
+
+        let spec = parse_local_spec(&format!("file://{}", repo.display())).unwrap();
+        assert_eq!(spec.path, "");
+
+        // Display roundtrips through parse_spec (agt.lock stores it)
+        let child = spec.with_path("development/my-skill");
+        let parsed = super::super::parse_spec(&child.to_string()).unwrap();
+        assert_eq!(parsed.transport, Transport::Local(repo));
+        assert_eq!(parsed.path, "development/my-skill");
+    }
+}
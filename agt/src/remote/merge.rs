@@ -0,0 +1,304 @@
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tar::{Archive, Builder};
+
+/// Pristine upstream snapshot kept next to `.remote-source` in copied skills.
+pub const BASE_FILE: &str = ".remote-base.tar.gz";
+
+/// How `merge_update` resolves files changed both locally and upstream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Write conflict markers (binary files and deletions keep the local copy)
+    #[default]
+    Merge,
+    /// Keep the local version of conflicting files
+    Ours,
+    /// Take the upstream version of conflicting files
+    Theirs,
+}
+
+/// Files `merge_update` did not simply take from upstream.
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    /// Local edits kept because upstream did not touch the file
+    pub kept: Vec<String>,
+    /// Local and upstream edits combined without conflicts
+    pub merged: Vec<String>,
+    /// Files changed on both sides that could not be merged cleanly
+    pub conflicts: Vec<String>,
+}
+
+/// Resolution of a single file.
+#[derive(Debug, PartialEq)]
+enum Resolution {
+    Ours,
+    Theirs,
+    Write(Vec<u8>),
+}
+
+/// Store the contents of `upstream` as the merge base of `skill_dir`.
+pub fn write_base(skill_dir: &Path, upstream: &Path) -> Result<()> {
+    let path = skill_dir.join(BASE_FILE);
+    let file = fs::File::create(&path).context(format!("Failed to write {}", path.display()))?;
+    let mut builder = Builder::new(GzEncoder::new(file, Compression::default()));
+    for (rel, file) in content_files(upstream)? {
+        builder.append_path_with_name(&file, &rel)?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Read the merge base of `skill_dir`, if one was recorded at install time.
+pub fn read_base(skill_dir: &Path) -> Result<Option<BTreeMap<String, Vec<u8>>>> {
+    let path = skill_dir.join(BASE_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let file = fs::File::open(&path)?;
+    let mut archive = Archive::new(GzDecoder::new(file));
+    let mut files = BTreeMap::new();
+    for entry in archive
+        .entries()
+        .context(format!("Failed to read {}", path.display()))?
+    {
+        let mut entry = entry?;
+        let rel = entry.path()?.to_string_lossy().replace('\\', "/");
+        let mut data = Vec::new();
+        std::io::Read::read_to_end(&mut entry, &mut data)?;
+        files.insert(rel, data);
+    }
+    Ok(Some(files))
+}
+
+/// Content hash of the upstream version `skill_dir` was installed from: its
+/// recorded base, or the directory itself when no base was recorded.
+pub fn upstream_hash(skill_dir: &Path) -> Result<String> {
+    match read_base(skill_dir)? {
+        Some(base) => Ok(crate::util::hash_tree(&base)),
+        None => crate::util::hash_dir(skill_dir),
+    }
+}
+
+/// Content files of a skill as relative path → bytes, without metadata.
+pub fn read_tree(dir: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
+    content_files(dir)?
+        .into_iter()
+        .map(|(rel, path)| Ok((rel, fs::read(&path)?)))
+        .collect()
+}
+
+/// Bring `skill_dir` up to `upstream` with a three-way merge against the
+/// recorded base, keeping local edits. Without a base every file is taken
+/// from upstream. The base itself is not refreshed here.
+pub fn merge_update(
+    skill_dir: &Path,
+    upstream: &Path,
+    strategy: MergeStrategy,
+) -> Result<MergeReport> {
+    let ours = read_tree(skill_dir)?;
+    let base = read_base(skill_dir)?.unwrap_or_else(|| ours.clone());
+    let theirs = read_tree(upstream)?;
+
+    let paths: BTreeSet<&String> = base.keys().chain(ours.keys()).chain(theirs.keys()).collect();
+    let mut report = MergeReport::default();
+    for rel in paths {
+        let b = base.get(rel).map(Vec::as_slice);
+        let o = ours.get(rel).map(Vec::as_slice);
+        let t = theirs.get(rel).map(Vec::as_slice);
+        let (resolution, conflict) = merge_file(b, o, t, strategy);
+
+        if conflict {
+            report.conflicts.push(rel.clone());
+        } else if o == t || resolution == Resolution::Theirs {
+            // Nothing local to report
+        } else if t == b {
+            report.kept.push(rel.clone());
+        } else {
+            report.merged.push(rel.clone());
+        }
+
+        let dest = skill_dir.join(rel);
+        match resolution {
+            Resolution::Ours => {}
+            Resolution::Theirs if t.is_some() => {
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                // fs::copy keeps the upstream file mode (executable scripts)
+                fs::copy(upstream.join(rel), &dest)?;
+            }
+            Resolution::Theirs => {
+                fs::remove_file(&dest)?;
+                remove_empty_parents(skill_dir, &dest);
+            }
+            Resolution::Write(data) => fs::write(&dest, data)?,
+        }
+    }
+    Ok(report)
+}
+
+/// Unified diff from `old` to `new`, labelled `a/<path>` and `b/<path>`.
+/// Empty when the trees are identical.
+pub fn diff_trees(
+    old: &BTreeMap<String, Vec<u8>>,
+    new: &BTreeMap<String, Vec<u8>>,
+    color: bool,
+) -> String {
+    let formatter = if color {
+        diffy::PatchFormatter::new().with_color()
+    } else {
+        diffy::PatchFormatter::new()
+    };
+    let mut out = String::new();
+    let paths: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for rel in paths {
+        let (a, b) = (old.get(rel), new.get(rel));
+        if a == b {
+            continue;
+        }
+        let a_text = as_text(a.map(Vec::as_slice));
+        let b_text = as_text(b.map(Vec::as_slice));
+        let (Some(a_text), Some(b_text)) = (a_text, b_text) else {
+            out.push_str(&format!("Binary files a/{} and b/{} differ\n", rel, rel));
+            continue;
+        };
+        let label = |prefix: &str, present: bool| {
+            if present {
+                format!("{}/{}", prefix, rel)
+            } else {
+                "/dev/null".to_string()
+            }
+        };
+        let mut options = diffy::DiffOptions::new();
+        options
+            .set_original_filename(label("a", a.is_some()))
+            .set_modified_filename(label("b", b.is_some()));
+        let patch = options.create_patch(a_text, b_text);
+        out.push_str(&formatter.fmt_patch(&patch).to_string());
+    }
+    out
+}
+
+/// Resolve one file from its base, local and upstream contents (`None` when
+/// absent). Returns the resolution and whether it was a conflict.
+fn merge_file(
+    base: Option<&[u8]>,
+    ours: Option<&[u8]>,
+    theirs: Option<&[u8]>,
+    strategy: MergeStrategy,
+) -> (Resolution, bool) {
+    if ours == theirs || theirs == base {
+        return (Resolution::Ours, false);
+    }
+    if ours == base {
+        return (Resolution::Theirs, false);
+    }
+
+    let conflicted = match (ours, theirs, as_text(base), as_text(ours), as_text(theirs)) {
+        (Some(_), Some(_), Some(b), Some(o), Some(t)) => match diffy::merge(b, o, t) {
+            Ok(merged) => return (Resolution::Write(merged.into_bytes()), false),
+            Err(conflicted) => Some(conflicted.into_bytes()),
+        },
+        // Deleted on one side, or binary
+        _ => None,
+    };
+    let resolution = match strategy {
+        MergeStrategy::Merge => conflicted.map_or(Resolution::Ours, Resolution::Write),
+        MergeStrategy::Ours => Resolution::Ours,
+        MergeStrategy::Theirs => Resolution::Theirs,
+    };
+    (resolution, true)
+}
+
+/// UTF-8 contents, with a missing file read as empty.
+fn as_text(data: Option<&[u8]>) -> Option<&str> {
+    std::str::from_utf8(data.unwrap_or_default()).ok()
+}
+
+/// Files under `dir` skipping top-level dot-prefixed metadata, like `util::hash_dir`.
+fn content_files(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    Ok(crate::util::list_files(dir)?
+        .into_iter()
+        .filter(|(rel, _)| !rel.starts_with('.'))
+        .collect())
+}
+
+fn remove_empty_parents(root: &Path, file: &Path) {
+    let mut dir = file.parent();
+    while let Some(d) = dir {
+        if d == root || fs::remove_dir(d).is_err() {
+            break;
+        }
+        dir = d.parent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, files: &[(&str, &str)]) {
+        for (rel, content) in files {
+            let path = dir.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_merge_update_keeps_local_edits() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let old = tmp.path().join("old");
+        let new = tmp.path().join("new");
+        let skill = tmp.path().join("skill");
+        write(&old, &[("SKILL.md", "a\nb\nc\n"), ("notes.md", "x\n"), ("gone.md", "g\n")]);
+        write(&new, &[("SKILL.md", "a\nb\nC\n"), ("notes.md", "y\n"), ("added.md", "n\n")]);
+        write(&skill, &[("SKILL.md", "A\nb\nc\n"), ("notes.md", "z\n"), ("gone.md", "g\n")]);
+        write(&skill, &[("mine.md", "m\n")]);
+        write_base(&skill, &old).unwrap();
+
+        let report = merge_update(&skill, &new, MergeStrategy::Merge).unwrap();
+        assert_eq!(report.merged, ["SKILL.md"]);
+        assert_eq!(report.kept, ["mine.md"]);
+        assert_eq!(report.conflicts, ["notes.md"]);
+
+        let tree = read_tree(&skill).unwrap();
+        assert_eq!(tree["SKILL.md"], b"A\nb\nC\n");
+        assert_eq!(tree["added.md"], b"n\n");
+        assert!(!tree.contains_key("gone.md"));
+        assert!(String::from_utf8_lossy(&tree["notes.md"]).contains("<<<<<<<"));
+    }
+
+    #[test]
+    fn test_diff_trees() {
+        let old = BTreeMap::from([
+            ("SKILL.md".to_string(), b"a\nb\n".to_vec()),
+            ("gone.md".to_string(), b"g\n".to_vec()),
+        ]);
+        let new = BTreeMap::from([
+            ("SKILL.md".to_string(), b"a\nc\n".to_vec()),
+            ("logo.png".to_string(), vec![0xff, 0xfe]),
+        ]);
+
+        let diff = diff_trees(&old, &new, false);
+        assert!(diff.contains("--- a/SKILL.md\n+++ b/SKILL.md\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n"));
+        assert!(diff.contains("--- a/gone.md\n+++ /dev/null\n"));
+        assert!(diff.contains("Binary files a/logo.png and b/logo.png differ"));
+        assert!(diff_trees(&old, &old, false).is_empty());
+    }
+
+    #[test]
+    fn test_merge_file_strategies() {
+        let (b, o, t) = (Some(&b"x\n"[..]), Some(&b"y\n"[..]), Some(&b"z\n"[..]));
+        assert_eq!(merge_file(b, o, t, MergeStrategy::Ours), (Resolution::Ours, true));
+        assert_eq!(merge_file(b, o, t, MergeStrategy::Theirs), (Resolution::Theirs, true));
+        // Local edit to a file upstream deleted is kept under the default strategy
+        assert_eq!(merge_file(b, o, None, MergeStrategy::Merge), (Resolution::Ours, true));
+        assert_eq!(merge_file(None, None, t, MergeStrategy::Merge), (Resolution::Theirs, false));
+    }
+}
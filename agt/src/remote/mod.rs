@@ -1,3 +1,25 @@
+mod cache;
+mod fetch;
+mod git;
+mod gitea;
 mod github;
+mod gitlab;
+mod index;
+mod local;
+mod merge;
+mod provider;
+mod verify;
+mod version;
 
+pub use cache::*;
+pub use fetch::*;
+pub use git::is_git_spec;
+pub use gitea::*;
 pub use github::*;
+pub use gitlab::*;
+pub use index::*;
+pub use local::is_local_spec;
+pub use merge::*;
+pub use provider::*;
+pub use verify::*;
+pub use version::*;
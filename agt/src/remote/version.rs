@@ -0,0 +1,143 @@
+use anyhow::{bail, Context, Result};
+use semver::{Version, VersionReq};
+
+use super::{git, RemoteSpec, Transport};
+
+/// Whether a `@ref` is a semver constraint (`^1.2`, `~1.4.0`, `>=1, <2`,
+/// `1.*`) rather than a branch, tag or commit.
+pub fn is_version_req(git_ref: &str) -> bool {
+    git_ref.starts_with(['^', '~', '>', '<', '=', '*']) || git_ref.ends_with(".*")
+}
+
+/// Parse a constraint, naming it in the error.
+pub fn parse_version_req(req: &str) -> Result<VersionReq> {
+    VersionReq::parse(req).context(format!("Invalid version constraint '{}'", req))
+}
+
+/// Parse a version, accepting a leading `v` and missing minor/patch (`v1.2`).
+pub fn parse_version(version: &str) -> Option<Version> {
+    let version = version.trim();
+    let version = version.strip_prefix('v').unwrap_or(version);
+    if let Ok(v) = Version::parse(version) {
+        return Some(v);
+    }
+    let parts: Vec<u64> = version
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts[..] {
+        [major] => Some(Version::new(major, 0, 0)),
+        [major, minor] => Some(Version::new(major, minor, 0)),
+        _ => None,
+    }
+}
+
+/// Version a tag releases for `skill`, and whether the tag is scoped to it.
+/// `<skill>-v1.2.3`, `<skill>/v1.2.3` and `<skill>@1.2.3` name one skill;
+/// `v1.2.3` and `1.2.3` release the whole repository.
+fn tag_version(tag: &str, skill: &str) -> Option<(bool, Version)> {
+    let scoped = ["-", "/", "@"].iter().find_map(|sep| {
+        tag.strip_prefix(skill)
+            .and_then(|rest| rest.strip_prefix(sep))
+            .and_then(parse_version)
+    });
+    match scoped {
+        Some(version) => Some((true, version)),
+        None => parse_version(tag).map(|version| (false, version)),
+    }
+}
+
+/// Highest tag matching `req`. Tags scoped to `skill` win over repository
+/// tags, so a monorepo can release skills independently.
+pub fn select_tag(tags: &[String], skill: &str, req: &VersionReq) -> Option<String> {
+    let versions: Vec<(bool, Version, &String)> = tags
+        .iter()
+        .filter_map(|tag| tag_version(tag, skill).map(|(scoped, v)| (scoped, v, tag)))
+        .filter(|(_, v, _)| req.matches(v))
+        .collect();
+    let scoped = versions.iter().any(|(scoped, _, _)| *scoped);
+    versions
+        .into_iter()
+        .filter(|(s, _, _)| *s == scoped)
+        .max_by(|a, b| a.1.cmp(&b.1))
+        .map(|(_, _, tag)| tag.clone())
+}
+
+/// Tags of the spec's repository.
+pub fn list_tags(spec: &RemoteSpec) -> Result<Vec<String>> {
+    match &spec.transport {
+        Transport::Git(url) => git::list_tags(url),
+        Transport::Local(_) | Transport::Tarball(_) => bail!(
+            "{} is a local source and has no tags; version constraints need a repository",
+            spec.repo_label()
+        ),
+        Transport::Archive => {
+            let provider = spec.provider();
+            let url = provider.tags_url(spec);
+            let body = provider
+                .get(&url)
+                .set("Accept", "application/json")
+                .call()
+                .context(format!(
+                    "Failed to list tags of {} via the {} API",
+                    spec.repo_label(),
+                    provider.name()
+                ))?
+                .into_string()
+                .context("Failed to read tags response")?;
+            Ok(provider.parse_tags(&body))
+        }
+    }
+}
+
+/// Point `git_ref` at the highest tag matching the spec's version constraint.
+/// Specs without a constraint are left as they are.
+pub fn resolve_version(spec: &mut RemoteSpec) -> Result<()> {
+    let Some(req) = spec.version_req.clone() else {
+        return Ok(());
+    };
+    let parsed = parse_version_req(&req)?;
+    let skill = spec.path.rsplit('/').next().unwrap_or_default().to_string();
+    let tags = list_tags(spec)?;
+    match select_tag(&tags, &skill, &parsed) {
+        Some(tag) => {
+            spec.git_ref = tag;
+            Ok(())
+        }
+        None => bail!("No tag of {} matches {}", spec.repo_label(), req),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_is_version_req() {
+        assert!(is_version_req("^1.2"));
+        assert!(is_version_req("~1.4.0"));
+        assert!(is_version_req(">=1, <2"));
+        assert!(is_version_req("1.*"));
+        assert!(!is_version_req("main"));
+        assert!(!is_version_req("v1.2.3"));
+    }
+
+    #[test]
+    fn test_select_tag() {
+        let req = parse_version_req("^1.2").unwrap();
+        let repo = tags(&["v1.1.0", "v1.2.0", "v1.3", "v2.0.0", "nightly"]);
+        assert_eq!(select_tag(&repo, "git-commit-pr", &req).as_deref(), Some("v1.3"));
+
+        // Skill-scoped tags take precedence over repository releases
+        let scoped = tags(&["v1.9.0", "git-commit-pr-v1.2.5", "git-commit-pr/v2.0.0", "other-v1.8.0"]);
+        assert_eq!(
+            select_tag(&scoped, "git-commit-pr", &req).as_deref(),
+            Some("git-commit-pr-v1.2.5")
+        );
+        assert_eq!(select_tag(&repo, "x", &parse_version_req("^3").unwrap()), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::config;
+use crate::{config, remote};
 use anyhow::{bail, Context, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
 use std::path::{Path, PathBuf};
@@ -104,10 +104,18 @@ fn run_interactive_selector_inner(
 
 fn select_profile(source_dir: &Path, theme: &ColorfulTheme, remote: bool) -> Result<InteractiveSelection> {
     let profiles = if remote {
-        config::list_profiles_remote(source_dir)
+        config::list_profiles_remote(source_dir)?
     } else {
-        config::list_profiles(source_dir)
+        config::list_profiles(source_dir)?
     };
+    pick_profile(&profiles, theme)
+}
+
+/// Select one of (name, description, skill count).
+fn pick_profile(
+    profiles: &[(String, String, usize)],
+    theme: &ColorfulTheme,
+) -> Result<InteractiveSelection> {
     if profiles.is_empty() {
         bail!("No profiles available");
     }
@@ -143,20 +151,43 @@ fn browse_by_group(
     global_installed: &[String],
     theme: &ColorfulTheme,
 ) -> Result<InteractiveSelection> {
-    let groups = config::skill_groups(source_dir);
-    if groups.is_empty() {
+    let catalog: Catalog = config::skill_groups(source_dir)
+        .into_iter()
+        .map(|group| {
+            let skills = config::skills_in_group(source_dir, &group)
+                .into_iter()
+                .map(|name| {
+                    let desc = read_skill_description(&source_dir.join(&group).join(&name));
+                    (name, desc)
+                })
+                .collect();
+            (group, skills)
+        })
+        .collect();
+    browse_catalog(&catalog, local_installed, global_installed, theme)
+}
+
+/// Skill groups with their (name, description) skills.
+type Catalog = Vec<(String, Vec<(String, String)>)>;
+
+fn browse_catalog(
+    catalog: &Catalog,
+    local_installed: &[String],
+    global_installed: &[String],
+    theme: &ColorfulTheme,
+) -> Result<InteractiveSelection> {
+    if catalog.is_empty() {
         bail!("No skill groups found");
     }
 
     // Build group display with counts
     let max_w = display_width();
-    let group_items: Vec<String> = groups
+    let group_items: Vec<String> = catalog
         .iter()
-        .map(|g| {
-            let skills = config::skills_in_group(source_dir, g);
+        .map(|(g, skills)| {
             let installed: usize = skills
                 .iter()
-                .filter(|s| local_installed.contains(s) || global_installed.contains(s))
+                .filter(|(s, _)| local_installed.contains(s) || global_installed.contains(s))
                 .count();
             truncate_for_display(
                 &format!("{:20} ({}/{} installed)", g, installed, skills.len()),
@@ -178,8 +209,7 @@ fn browse_by_group(
         Some(idx) => idx,
     };
 
-    let group = &groups[group_idx];
-    let skills = config::skills_in_group(source_dir, group);
+    let (group, skills) = &catalog[group_idx];
 
     if skills.is_empty() {
         bail!("No skills in group '{}'", group);
@@ -189,7 +219,7 @@ fn browse_by_group(
     let max_w = display_width();
     let items: Vec<String> = skills
         .iter()
-        .map(|name| {
+        .map(|(name, desc)| {
             let tag = if local_installed.contains(name) {
                 "[L]"
             } else if global_installed.contains(name) {
@@ -197,7 +227,6 @@ fn browse_by_group(
             } else {
                 "[ ]"
             };
-            let desc = read_skill_description(&source_dir.join(group).join(name));
             let raw = if desc.is_empty() {
                 format!("{} {}", tag, name)
             } else {
@@ -223,13 +252,60 @@ fn browse_by_group(
         Some(indices) => {
             let selected: Vec<(String, String)> = indices
                 .into_iter()
-                .map(|i| (group.clone(), skills[i].clone()))
+                .map(|i| (group.clone(), skills[i].0.clone()))
                 .collect();
             Ok(InteractiveSelection::Skills(selected))
         }
     }
 }
 
+/// Interactive selector over a remote repository's published index, so the
+/// menu shows before anything is downloaded.
+pub fn run_index_selector(
+    index: &remote::CatalogIndex,
+    local_installed: &[String],
+    global_installed: &[String],
+) -> Result<InteractiveSelection> {
+    let theme = ColorfulTheme::default();
+    let modes = ["Install a profile (curated set)", "Browse by group"];
+    let mode = Select::with_theme(&theme)
+        .with_prompt("How would you like to install skills?")
+        .items(modes)
+        .default(0)
+        .max_length(PAGE_SIZE)
+        .interact_opt()
+        .context("Failed to render mode selection")?;
+
+    match mode {
+        None => Ok(InteractiveSelection::Cancelled),
+        Some(0) => {
+            let mut profiles: Vec<(String, String, usize)> = index
+                .profiles
+                .iter()
+                .map(|p| (p.name.clone(), p.description.clone(), p.skills.len()))
+                .collect();
+            profiles.push((
+                "all".to_string(),
+                "All available skills".to_string(),
+                index.skills.len(),
+            ));
+            profiles.sort_by(|a, b| a.0.cmp(&b.0));
+            pick_profile(&profiles, &theme)
+        }
+        _ => {
+            let mut catalog: Catalog = Vec::new();
+            for skill in &index.skills {
+                let entry = (skill.name.clone(), skill.description.clone());
+                match catalog.iter_mut().find(|(g, _)| *g == skill.group) {
+                    Some((_, skills)) => skills.push(entry),
+                    None => catalog.push((skill.group.clone(), vec![entry])),
+                }
+            }
+            browse_catalog(&catalog, local_installed, global_installed, &theme)
+        }
+    }
+}
+
 pub fn run_no_source_selector(
     cwd_source: Option<PathBuf>,
 ) -> Result<InteractiveSelection> {
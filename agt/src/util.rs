@@ -1,6 +1,7 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Validate a skill/persona name to prevent path traversal and catch argument mistakes
 pub fn validate_name(name: &str) -> Result<()> {
@@ -24,21 +25,176 @@ pub fn validate_name(name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Check if target path exists and clear it if force is set
-pub fn ensure_target_clear(path: &Path, force: bool, entity_name: &str) -> Result<()> {
-    if path.exists() || path.is_symlink() {
-        if force {
-            if path.is_symlink() || path.is_file() {
-                fs::remove_file(path)?;
-            } else {
-                fs::remove_dir_all(path)?;
-            }
+/// Whether a value is a full 40-character commit SHA.
+pub fn is_commit_sha(value: &str) -> bool {
+    value.len() == 40 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Replace `path` with content that `populate` builds at a staging path next
+/// to it, so a failed download or copy leaves the current install untouched.
+/// The swap is two renames within the install directory; only then is the
+/// replaced version moved to the backup area for `restore_backup`.
+/// Without `force`, an existing `path` is an error.
+pub fn install_atomic(
+    path: &Path,
+    force: bool,
+    entity_name: &str,
+    populate: impl FnOnce(&Path) -> Result<()>,
+) -> Result<()> {
+    let exists = path.exists() || path.is_symlink();
+    if exists && !force {
+        bail!(
+            "'{}' already installed at {}. Use --force to overwrite.",
+            entity_name,
+            path.display()
+        );
+    }
+
+    let staging = sibling(path, "new")?;
+    remove_path(&staging)?;
+    if let Err(e) = populate(&staging) {
+        let _ = remove_path(&staging);
+        return Err(e);
+    }
+
+    if !exists {
+        return fs::rename(&staging, path)
+            .with_context(|| format!("Failed to install {}", path.display()));
+    }
+
+    let old = sibling(path, "old")?;
+    remove_path(&old)?;
+    if let Err(e) = fs::rename(path, &old) {
+        let _ = remove_path(&staging);
+        let e = anyhow::Error::new(e);
+        return Err(e.context(format!("Failed to move aside {}", path.display())));
+    }
+    if let Err(e) = fs::rename(&staging, path) {
+        let _ = fs::rename(&old, path);
+        let _ = remove_path(&staging);
+        let e = anyhow::Error::new(e);
+        return Err(e.context(format!("Failed to replace {}", path.display())));
+    }
+
+    // The new version is in place; keeping the old one is best effort
+    let backup = backup_path(path)?;
+    if let Err(e) = remove_path(&backup).and_then(|_| move_path(&old, &backup)) {
+        crate::ui::warn(&format!("Could not back up {}: {:#}", path.display(), e));
+        let _ = remove_path(&old);
+    }
+    Ok(())
+}
+
+/// Swap `path` with the version `install_atomic` backed up, so a second
+/// restore undoes the first. The backup is first moved next to `path`, so the
+/// swap itself is two renames.
+pub fn restore_backup(path: &Path, entity_name: &str) -> Result<()> {
+    let backup = backup_path(path)?;
+    if !backup.exists() && !backup.is_symlink() {
+        bail!("No previous version of '{}' to roll back to", entity_name);
+    }
+
+    let incoming = sibling(path, "new")?;
+    remove_path(&incoming)?;
+    if let Err(e) = move_path(&backup, &incoming) {
+        let _ = remove_path(&incoming);
+        return Err(e.context(format!("Failed to read backup of {}", path.display())));
+    }
+
+    let current = sibling(path, "old")?;
+    remove_path(&current)?;
+    let exists = path.exists() || path.is_symlink();
+    if exists {
+        fs::rename(path, &current)?;
+    }
+    if let Err(e) = fs::rename(&incoming, path) {
+        if exists {
+            let _ = fs::rename(&current, path);
+        }
+        let _ = move_path(&incoming, &backup);
+        let e = anyhow::Error::new(e);
+        return Err(e.context(format!("Failed to restore {}", path.display())));
+    }
+    if exists {
+        move_path(&current, &backup)?;
+    }
+    Ok(())
+}
+
+/// Remove an installed item by moving it to the backup area, where
+/// `restore_backup` can bring it back.
+pub fn remove_to_backup(path: &Path) -> Result<()> {
+    let backup = backup_path(path)?;
+    remove_path(&backup)?;
+    move_path(path, &backup).with_context(|| format!("Failed to remove {}", path.display()))
+}
+
+/// Backup location of an installed item: its absolute path mirrored under
+/// `config::backup_dir()`.
+pub fn backup_path(path: &Path) -> Result<PathBuf> {
+    let parent = path.parent().context("Invalid install path")?;
+    let name = path.file_name().context("Invalid install path")?;
+    let absolute = fs::canonicalize(parent)
+        .or_else(|_| std::path::absolute(parent))
+        .with_context(|| format!("Failed to resolve {}", parent.display()))?
+        .join(name);
+    let relative = absolute.strip_prefix("/").unwrap_or(&absolute);
+    Ok(crate::config::backup_dir().join(relative))
+}
+
+/// Hidden `.<name>.agt-<suffix>` path next to `path`, on the same filesystem.
+fn sibling(path: &Path, suffix: &str) -> Result<PathBuf> {
+    let parent = path.parent().context("Invalid install path")?;
+    let name = path.file_name().context("Invalid install path")?;
+    fs::create_dir_all(parent)?;
+    Ok(parent.join(format!(".{}.agt-{}", name.to_string_lossy(), suffix)))
+}
+
+/// Remove a file, symlink or directory; missing paths are fine.
+pub fn remove_path(path: &Path) -> Result<()> {
+    if path.is_symlink() || path.is_file() {
+        fs::remove_file(path)?;
+    } else if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    }
+    Ok(())
+}
+
+/// Move a file, symlink or directory, copying when `rename` cannot cross
+/// filesystems.
+fn move_path(src: &Path, dst: &Path) -> Result<()> {
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    if src.is_symlink() {
+        std::os::unix::fs::symlink(fs::read_link(src)?, dst)?;
+    } else if src.is_dir() {
+        copy_tree(src, dst)?;
+    } else {
+        fs::copy(src, dst)?;
+    }
+    remove_path(src)
+}
+
+/// Copy an installed tree as it is, symlinks included. Unlike
+/// `copy_dir_recursive` this is only for agt's own installs and backups,
+/// never for fetched content.
+fn copy_tree(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(&src_path)?, &dst_path)?;
+        } else if file_type.is_dir() {
+            copy_tree(&src_path, &dst_path)?;
         } else {
-            bail!(
-                "'{}' already installed at {}. Use --force to overwrite.",
-                entity_name,
-                path.display()
-            );
+            fs::copy(&src_path, &dst_path)?;
         }
     }
     Ok(())
@@ -68,3 +224,119 @@ pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+/// Content hash of a directory (or single file) as `sha256:<hex>`.
+/// Covers relative paths and file contents in sorted order, skipping symlinks
+/// and top-level dot-prefixed metadata such as `.remote-source`.
+pub fn hash_dir(path: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    if path.is_file() {
+        files.push((String::new(), path.to_path_buf()));
+    } else {
+        collect_files(path, "", true, &mut files)?;
+    }
+    let mut tree = BTreeMap::new();
+    for (rel, file) in files {
+        tree.insert(rel, fs::read(&file)?);
+    }
+    Ok(hash_tree(&tree))
+}
+
+/// Content hash of in-memory files (relative path → bytes), matching `hash_dir`.
+pub fn hash_tree(files: &BTreeMap<String, Vec<u8>>) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for (rel, data) in files {
+        hasher.update(rel.as_bytes());
+        hasher.update([0]);
+        hasher.update(data);
+        hasher.update([0]);
+    }
+    format!("sha256:{}", hex(&hasher.finalize()))
+}
+
+/// Every regular file under `dir` as (`/`-separated relative path, path), sorted.
+/// Symlinks are skipped, matching `copy_dir_recursive`.
+pub fn list_files(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    collect_files(dir, "", false, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    skip_hidden: bool,
+    files: &mut Vec<(String, PathBuf)>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if skip_hidden && prefix.is_empty() && name.starts_with('.') {
+            continue;
+        }
+        let file_type = match entry.file_type() {
+            Ok(ft) => ft,
+            Err(_) => continue,
+        };
+        if file_type.is_symlink() {
+            continue;
+        }
+        let rel = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+        if file_type.is_dir() {
+            collect_files(&entry.path(), &rel, skip_hidden, files)?;
+        } else {
+            files.push((rel, entry.path()));
+        }
+    }
+    Ok(())
+}
+
+/// Lowercase hex encoding
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_atomic_failure_keeps_existing() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dest = tmp.path().join("skill");
+        fs::create_dir_all(&dest).unwrap();
+        fs::write(dest.join("SKILL.md"), "old").unwrap();
+
+        let result = install_atomic(&dest, true, "skill", |staging| {
+            fs::create_dir_all(staging)?;
+            fs::write(staging.join("SKILL.md"), "partial")?;
+            bail!("download failed")
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(dest.join("SKILL.md")).unwrap(), "old");
+        assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 1);
+
+        let refused = install_atomic(&dest, false, "skill", |_| Ok(()));
+        assert!(refused.unwrap_err().to_string().contains("already installed"));
+    }
+
+    #[test]
+    fn test_copy_tree_keeps_symlinks() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(src.join("scripts")).unwrap();
+        fs::write(src.join("SKILL.md"), "skill").unwrap();
+        std::os::unix::fs::symlink("../SKILL.md", src.join("scripts/link.md")).unwrap();
+
+        let dst = tmp.path().join("dst");
+        copy_tree(&src, &dst).unwrap();
+        assert_eq!(fs::read_link(dst.join("scripts/link.md")).unwrap(), Path::new("../SKILL.md"));
+        assert_eq!(fs::read_to_string(dst.join("SKILL.md")).unwrap(), "skill");
+    }
+}